@@ -0,0 +1,270 @@
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use syn::{Attribute, Data, DeriveInput, Fields, Ident, LitStr, parse_macro_input, Type};
+
+/// Implements `FromStr` from a line format, the error being `advent_of_code::ParseError`.
+///
+/// On a struct, `#[aoc(format = "...")]` gives the text of a line with a `{field}` placeholder for
+/// each field (`{0}`, `{1}`... on tuple structs), `{{` and `}}` being literal braces. Every field is
+/// read up to the text following it, trimmed and parsed with its own `FromStr`:
+/// - `{field:sep(';')}` reads a `Vec` of items separated by `;`
+/// - an `Option` field is `None` when its text is blank
+///
+/// On an enum, unit variants are read from a keyword, their lowercase name unless given with
+/// `#[aoc(keyword = "...")]`, and variants with fields from their own `#[aoc(format = "...")]`.
+/// Variants are tried in order.
+///
+/// ```ignore
+/// #[derive(AocParse)]
+/// #[aoc(format = "Game {id}: {draws:sep(';')}")]
+/// struct Game {
+///     id: u32,
+///     draws: Vec<Draw>,
+/// }
+/// ```
+#[proc_macro_derive(AocParse, attributes(aoc))]
+pub fn derive_aoc_parse(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input).unwrap_or_else(syn::Error::into_compile_error).into()
+}
+
+enum Segment {
+    Literal(String),
+    Field {
+        name: String,
+        separator: Option<String>,
+    },
+}
+
+// `(format, keyword)` of an `#[aoc(...)]` attribute
+fn aoc_attribute(attrs: &[Attribute]) -> syn::Result<(Option<LitStr>, Option<LitStr>)> {
+    let (mut format, mut keyword) = (None, None);
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("aoc")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("format") {
+                format = Some(meta.value()?.parse()?);
+                Ok(())
+            } else if meta.path.is_ident("keyword") {
+                keyword = Some(meta.value()?.parse()?);
+                Ok(())
+            } else {
+                Err(meta.error("expected `format` or `keyword`"))
+            }
+        })?;
+    }
+    Ok((format, keyword))
+}
+
+fn parse_format(format: &LitStr) -> syn::Result<Vec<Segment>> {
+    let error = |message: &str| syn::Error::new(format.span(), message);
+    let text = format.value();
+    let mut segments = vec![];
+    let mut literal = String::new();
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                literal.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                literal.push('}');
+            }
+            '}' => return Err(error("unmatched `}` in format, use `}}` for a literal brace")),
+            '{' => {
+                let placeholder: String = chars.by_ref().take_while(|&c| c != '}').collect();
+                if !literal.is_empty() {
+                    segments.push(Segment::Literal(std::mem::take(&mut literal)));
+                } else if matches!(segments.last(), Some(Segment::Field { .. })) {
+                    return Err(error("two fields must be separated by some text"));
+                }
+
+                let (name, spec) = match placeholder.split_once(':') {
+                    Some((name, spec)) => (name, Some(spec.trim())),
+                    None => (placeholder.as_str(), None),
+                };
+                let separator = match spec {
+                    None => None,
+                    Some(spec) => {
+                        let quoted = spec
+                            .strip_prefix("sep(")
+                            .and_then(|spec| spec.strip_suffix(')'))
+                            .map(str::trim)
+                            .ok_or_else(|| error("expected `sep('...')` after `:`"))?;
+                        let separator = quoted
+                            .strip_prefix('\'')
+                            .and_then(|s| s.strip_suffix('\''))
+                            .or_else(|| quoted.strip_prefix('"').and_then(|s| s.strip_suffix('"')))
+                            .filter(|s| !s.is_empty())
+                            .ok_or_else(|| error("the separator must be a non-empty quoted string"))?;
+                        Some(separator.to_string())
+                    }
+                };
+
+                segments.push(Segment::Field {
+                    name: name.trim().to_string(),
+                    separator,
+                });
+            }
+            c => literal.push(c),
+        }
+    }
+    if !literal.is_empty() {
+        segments.push(Segment::Literal(literal));
+    }
+
+    Ok(segments)
+}
+
+fn is_option(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) => path.path.segments.last().is_some_and(|segment| segment.ident == "Option"),
+        _ => false,
+    }
+}
+
+// Statements reading the fields of `fields` into variables, and the expression building `constructor` from them
+fn parse_fields(format: &LitStr, fields: &Fields, constructor: TokenStream2) -> syn::Result<TokenStream2> {
+    let segments = parse_format(format)?;
+
+    // (name in the format, variable, is optional)
+    let bindings: Vec<(String, Ident, bool)> = fields
+        .iter()
+        .enumerate()
+        .map(|(i, field)| match &field.ident {
+            Some(ident) => (ident.to_string(), ident.clone(), is_option(&field.ty)),
+            None => (i.to_string(), format_ident!("field_{}", i), is_option(&field.ty)),
+        })
+        .collect();
+
+    let mut statements = vec![];
+    let mut used = vec![false; bindings.len()];
+    for (i, segment) in segments.iter().enumerate() {
+        match segment {
+            Segment::Literal(literal) if i == 0 => statements.push(quote! { parser.literal(#literal)?; }),
+            // Consumed with the field before it
+            Segment::Literal(_) => {}
+            Segment::Field { name, separator } => {
+                let Some(index) = bindings.iter().position(|(field, _, _)| field == name) else {
+                    return Err(syn::Error::new(format.span(), format!("no field `{name}`")));
+                };
+                if std::mem::replace(&mut used[index], true) {
+                    return Err(syn::Error::new(format.span(), format!("field `{name}` appears twice")));
+                }
+
+                let until = match segments.get(i + 1) {
+                    Some(Segment::Literal(literal)) => quote! { ::core::option::Option::Some(#literal) },
+                    _ => quote! { ::core::option::Option::None },
+                };
+                let (_, variable, optional) = &bindings[index];
+                statements.push(match (separator, optional) {
+                    (Some(separator), _) => quote! { let #variable = parser.list(#name, #separator, #until)?; },
+                    (None, true) => quote! { let #variable = parser.optional(#name, #until)?; },
+                    (None, false) => quote! { let #variable = parser.field(#name, #until)?; },
+                });
+            }
+        }
+    }
+
+    if let Some(index) = used.iter().position(|used| !used) {
+        let message = format!("field `{}` is missing from the format", bindings[index].0);
+        return Err(syn::Error::new(format.span(), message));
+    }
+
+    let variables = bindings.iter().map(|(_, variable, _)| variable);
+    let value = match fields {
+        Fields::Named(_) => quote! { #constructor { #(#variables),* } },
+        Fields::Unnamed(_) => quote! { #constructor ( #(#variables),* ) },
+        Fields::Unit => quote! { #constructor },
+    };
+
+    Ok(quote! {
+        let mut parser = ::advent_of_code::line_format::LineParser::new(input);
+        #(#statements)*
+        parser.end()?;
+        ::core::result::Result::Ok(#value)
+    })
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(&input.generics, "AocParse doesn't support generic types"));
+    }
+
+    let body = match &input.data {
+        Data::Struct(data) => {
+            let (Some(format), _) = aoc_attribute(&input.attrs)? else {
+                return Err(syn::Error::new(Span::call_site(), "expected `#[aoc(format = \"...\")]`"));
+            };
+            parse_fields(&format, &data.fields, quote! { #name })?
+        }
+        Data::Enum(data) => {
+            let mut attempts = vec![];
+            let mut expected = vec![];
+            for variant in data.variants.iter() {
+                let ident = &variant.ident;
+                let (format, keyword) = aoc_attribute(&variant.attrs)?;
+
+                match (&variant.fields, format) {
+                    (Fields::Unit, _) => {
+                        let keyword = keyword.map_or_else(|| ident.to_string().to_lowercase(), |keyword| keyword.value());
+                        expected.push(format!("`{keyword}`"));
+                        attempts.push(quote! {
+                            if input.trim() == #keyword {
+                                return ::core::result::Result::Ok(#name::#ident);
+                            }
+                        });
+                    }
+                    (_, Some(format)) => {
+                        expected.push(format!("`{}`", format.value()));
+                        let parse = parse_fields(&format, &variant.fields, quote! { #name::#ident })?;
+                        let function = format_ident!("parse_{}", ident.to_string().to_lowercase());
+                        attempts.push(quote! {
+                            fn #function(input: &str) -> ::core::result::Result<#name, ::advent_of_code::ParseError> {
+                                #parse
+                            }
+                            match #function(input) {
+                                ::core::result::Result::Ok(value) => return ::core::result::Result::Ok(value),
+                                ::core::result::Result::Err(e) => {
+                                    if best.as_ref().is_none_or(|best: &::advent_of_code::ParseError| e.column > best.column) {
+                                        best = ::core::option::Option::Some(e);
+                                    }
+                                }
+                            }
+                        });
+                    }
+                    (_, None) => {
+                        return Err(syn::Error::new_spanned(variant, "expected `#[aoc(format = \"...\")]` on a variant with fields"));
+                    }
+                }
+            }
+
+            let expected = format!("one of {}", expected.join(", "));
+            quote! {
+                // Error of the variant that went the furthest, if one went past the first character
+                let mut best: ::core::option::Option<::advent_of_code::ParseError> = ::core::option::Option::None;
+                #(#attempts)*
+                match best {
+                    ::core::option::Option::Some(e) if e.column > 1 => ::core::result::Result::Err(e),
+                    _ => ::core::result::Result::Err(::advent_of_code::ParseError::new(1, 1, input, #expected)),
+                }
+            }
+        }
+        Data::Union(_) => return Err(syn::Error::new(Span::call_site(), "AocParse doesn't support unions")),
+    };
+
+    Ok(quote! {
+        impl ::core::str::FromStr for #name {
+            type Err = ::advent_of_code::ParseError;
+
+            #[allow(unused_mut)]
+            fn from_str(input: &str) -> ::core::result::Result<Self, Self::Err> {
+                #body
+            }
+        }
+    })
+}
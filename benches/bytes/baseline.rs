@@ -0,0 +1,70 @@
+//! The days as they were before reading their input as bytes, to compare the ported ones with.
+
+pub mod day_2023_01 {
+    use itertools::Itertools;
+    use nom::FindSubstring;
+
+    pub fn part_one(input: &str) -> Option<u32> {
+        let response = input.lines().map(|line| {
+            let numeric_chars: Vec<_> = line.chars().filter(|x| x.is_numeric()).collect();
+            format!("{}{}",
+                    numeric_chars.first().unwrap(),
+                    numeric_chars.last().unwrap()
+            )
+                .parse::<u32>()
+                .unwrap()
+        }).sum();
+        Some(response)
+    }
+
+    const INT_STRINGS: [&str; 9] = ["one", "two", "three", "four", "five", "six", "seven", "eight", "nine"];
+
+    // Lines are owned rather than leaked as they were, so that runs don't pile up memory
+    pub fn part_two(input: &str) -> Option<u32> {
+        let response = input.lines().map(|line| {
+            let mut line = line.to_string();
+
+            // Replaces 9 -> 9e, keeping the last char to be reused by an overlapping digit
+            while let Some((_, i)) = INT_STRINGS
+                .iter()
+                .enumerate()
+                .filter_map(|(index, &int_string)| line.as_str().find_substring(int_string).map(|i| (i, index)))
+                .sorted_by(|(a, _), (b, _)| Ord::cmp(b, a))
+                .next_back() {
+                let last_char = INT_STRINGS[i].chars().last().unwrap();
+                line = line.replacen(INT_STRINGS[i], &format!("{}{}", i + 1, last_char), 1);
+            }
+
+            let numeric_chars: Vec<_> = line.chars().filter(|x| x.is_numeric()).collect();
+            format!("{}{}",
+                    numeric_chars.first().unwrap(),
+                    numeric_chars.last().unwrap()
+            )
+                .parse::<u32>()
+                .unwrap()
+        }).sum();
+        Some(response)
+    }
+}
+
+pub mod day_2023_12 {
+    /// Character before each position of every pattern, as the day looked it up.
+    pub fn chars_before(input: &str) -> usize {
+        patterns(input)
+            .flat_map(|pattern| (1..pattern.len()).map(move |i| pattern.chars().nth(i - 1).unwrap()))
+            .filter(|&c| c == '#')
+            .count()
+    }
+
+    /// Same lookup on the bytes, as the day does now.
+    pub fn bytes_before(input: &str) -> usize {
+        patterns(input)
+            .flat_map(|pattern| (1..pattern.len()).map(move |i| pattern.as_bytes()[i - 1]))
+            .filter(|&byte| byte == b'#')
+            .count()
+    }
+
+    fn patterns(input: &str) -> impl Iterator<Item=&str> {
+        input.lines().filter_map(|line| line.split_once(' ')).map(|(pattern, _)| pattern)
+    }
+}
@@ -0,0 +1,82 @@
+//! Times the days reading their input as bytes against their versions going through `chars()`, and
+//! the grid readers of the library against each other. Days run on their `input.txt` when it's
+//! there, on their example otherwise.
+//!
+//! Run with `cargo bench --bench bytes`.
+
+use std::hint::black_box;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use advent_of_code::bytes::ByteGrid;
+use advent_of_code::{get_input_path, input_to_grid, input_to_grid_with, read_example, read_example_2, read_input};
+
+// The days themselves, linted as binaries
+#[allow(dead_code, unused_imports, clippy::all)]
+#[path = "../../src/bin/2023-01/main.rs"]
+mod day_2023_01;
+#[allow(dead_code, unused_imports, clippy::all)]
+#[path = "../../src/bin/2023-12/main.rs"]
+mod day_2023_12;
+mod baseline;
+
+const RUNS: u32 = 50;
+
+// Best time of `RUNS` runs, less noisy than the mean
+fn best_time<T>(mut run: impl FnMut() -> T) -> Duration {
+    (0..RUNS)
+        .map(|_| {
+            let now = Instant::now();
+            black_box(run());
+            now.elapsed()
+        })
+        .min()
+        .unwrap()
+}
+
+fn report(name: &str, time: Duration) {
+    println!("{name:<48} {:>12.2}μs", time.as_secs_f64() * 1e6);
+}
+
+fn day_input(day: u8, year: u16, example: fn(u8, u16) -> String) -> (String, &'static str) {
+    if Path::new(&get_input_path(day, year)).exists() {
+        (read_input(day, year), "input")
+    } else {
+        (example(day, year), "example")
+    }
+}
+
+fn main() {
+    let (input, source) = day_input(1, 2023, read_example);
+    assert_eq!(baseline::day_2023_01::part_one(&input), day_2023_01::part_one(&input));
+    report(&format!("2023-01 part one: chars ({source})"), best_time(|| baseline::day_2023_01::part_one(&input)));
+    report(&format!("2023-01 part one: bytes ({source})"), best_time(|| day_2023_01::part_one(&input)));
+    let (input, source) = day_input(1, 2023, read_example_2);
+    assert_eq!(baseline::day_2023_01::part_two(&input), day_2023_01::part_two(&input));
+    report(&format!("2023-01 part two: chars ({source})"), best_time(|| baseline::day_2023_01::part_two(&input)));
+    report(&format!("2023-01 part two: bytes ({source})"), best_time(|| day_2023_01::part_two(&input)));
+
+    // Only the lookup of the character before each position went from chars to bytes
+    let (input, source) = day_input(12, 2023, read_example);
+    assert_eq!(baseline::day_2023_12::chars_before(&input), baseline::day_2023_12::bytes_before(&input));
+    report(&format!("2023-12 character before: chars ({source})"), best_time(|| baseline::day_2023_12::chars_before(&input)));
+    report(&format!("2023-12 character before: bytes ({source})"), best_time(|| baseline::day_2023_12::bytes_before(&input)));
+    report(&format!("2023-12 part one ({source})"), best_time(|| day_2023_12::part_one(&input)));
+    report(&format!("2023-12 part two ({source})"), best_time(|| day_2023_12::part_two(&input)));
+
+    // Reading a grid and looking at every cell, on a grid day
+    let (input, source) = day_input(10, 2023, read_example);
+    let count = |cells: &mut dyn Iterator<Item=u8>| cells.filter(|&cell| cell == b'|').count();
+    report(
+        &format!("2023-10 grid: input_to_grid::<char> ({source})"),
+        best_time(|| count(&mut input_to_grid::<char>(&input).unwrap().iter().map(|&c| c as u8))),
+    );
+    report(
+        &format!("2023-10 grid: input_to_grid_with ({source})"),
+        best_time(|| count(&mut input_to_grid_with(&input, Ok::<u8, String>).unwrap().iter().copied())),
+    );
+    report(
+        &format!("2023-10 grid: ByteGrid ({source})"),
+        best_time(|| count(&mut ByteGrid::new(input.as_bytes()).unwrap().indexed_iter().map(|(_, cell)| cell))),
+    );
+}
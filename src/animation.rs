@@ -0,0 +1,309 @@
+use std::fmt::Display;
+use std::io::{stdin, stdout, Write};
+#[cfg(not(debug_assertions))]
+use std::marker::PhantomData;
+use std::sync::mpsc::{channel, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+
+use crate::CustomGrid;
+use crate::render::Renderer;
+
+/// Snapshots of a simulation, to be replayed with a [`Player`].
+///
+/// Recording only happens in debug builds: in release builds (benchmark runs) the recorder holds
+/// nothing and [`Recorder::record`] does nothing, so it can be left in solutions.
+pub struct Recorder<T> {
+    #[cfg(debug_assertions)]
+    frames: Vec<CustomGrid<T>>,
+    #[cfg(not(debug_assertions))]
+    frames: PhantomData<T>,
+}
+
+impl<T: Clone> Default for Recorder<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone> Recorder<T> {
+    #[cfg(debug_assertions)]
+    pub fn new() -> Self {
+        Self {
+            frames: vec![],
+        }
+    }
+
+    #[cfg(not(debug_assertions))]
+    pub fn new() -> Self {
+        Self {
+            frames: PhantomData,
+        }
+    }
+
+    #[cfg(debug_assertions)]
+    pub fn record(&mut self, grid: &CustomGrid<T>) {
+        self.frames.push(grid.clone());
+    }
+
+    #[cfg(not(debug_assertions))]
+    #[inline(always)]
+    pub fn record(&mut self, _grid: &CustomGrid<T>) {}
+
+    #[cfg(debug_assertions)]
+    pub fn frames(&self) -> &[CustomGrid<T>] {
+        &self.frames
+    }
+
+    #[cfg(not(debug_assertions))]
+    pub fn frames(&self) -> &[CustomGrid<T>] {
+        &[]
+    }
+
+    pub fn len(&self) -> usize {
+        self.frames().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames().is_empty()
+    }
+}
+
+/// Player controls, typed in the terminal followed by enter.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Command {
+    /// `p` or an empty line
+    PlayPause,
+    /// `n`
+    Next,
+    /// `b`
+    Back,
+    /// `+`
+    Faster,
+    /// `-`
+    Slower,
+    /// `q`
+    Quit,
+}
+
+impl Command {
+    pub fn parse(line: &str) -> Option<Command> {
+        match line.trim() {
+            "" | "p" => Some(Command::PlayPause),
+            "n" => Some(Command::Next),
+            "b" => Some(Command::Back),
+            "+" => Some(Command::Faster),
+            "-" => Some(Command::Slower),
+            "q" => Some(Command::Quit),
+            _ => None,
+        }
+    }
+}
+
+const MIN_DELAY: Duration = Duration::from_millis(10);
+const MAX_DELAY: Duration = Duration::from_secs(5);
+
+/// Where a [`Player`] is in the recording.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct PlayerState {
+    pub frame: usize,
+    pub playing: bool,
+    pub delay: Duration,
+    pub quit: bool,
+}
+
+impl PlayerState {
+    /// Applies `command` for a recording of `frames` frames.
+    pub fn apply(&mut self, command: Command, frames: usize) {
+        let last = frames.saturating_sub(1);
+        match command {
+            Command::PlayPause => {
+                // Playing again from the end restarts the recording
+                if !self.playing && self.frame == last {
+                    self.frame = 0;
+                }
+                self.playing = !self.playing
+            }
+            Command::Next => {
+                self.playing = false;
+                self.frame = (self.frame + 1).min(last);
+            }
+            Command::Back => {
+                self.playing = false;
+                self.frame = self.frame.saturating_sub(1);
+            }
+            Command::Faster => self.delay = (self.delay / 2).max(MIN_DELAY),
+            Command::Slower => self.delay = (self.delay * 2).min(MAX_DELAY),
+            Command::Quit => self.quit = true,
+        }
+    }
+
+    /// Moves to the next frame while playing, pausing on the last one.
+    pub fn tick(&mut self, frames: usize) {
+        if self.frame + 1 >= frames {
+            self.playing = false;
+        } else {
+            self.frame += 1;
+        }
+    }
+}
+
+/// Replays the frames of a [`Recorder`] in the terminal.
+pub struct Player<'a, T> {
+    frames: &'a [CustomGrid<T>],
+    renderer: Renderer,
+    delay: Duration,
+}
+
+impl<'a, T: Clone + Display> Player<'a, T> {
+    pub fn new(recorder: &'a Recorder<T>) -> Self {
+        Self {
+            frames: recorder.frames(),
+            renderer: Renderer::new(),
+            delay: Duration::from_millis(200),
+        }
+    }
+
+    /// Renderer used to draw each frame, e.g. to crop a big grid.
+    pub fn renderer(self, renderer: Renderer) -> Self {
+        Self {
+            renderer,
+            ..self
+        }
+    }
+
+    /// Initial time between two frames while playing.
+    pub fn delay(self, delay: Duration) -> Self {
+        Self {
+            delay,
+            ..self
+        }
+    }
+
+    /// Plays the recording until `q` is typed.
+    pub fn play(&self) {
+        if self.frames.is_empty() {
+            println!("Nothing recorded");
+            return;
+        }
+
+        let (sender, receiver) = channel();
+        thread::spawn(move || {
+            for line in stdin().lines() {
+                let Ok(line) = line else { break };
+                if let Some(command) = Command::parse(&line) {
+                    if sender.send(command).is_err() {
+                        break;
+                    }
+                }
+            }
+        });
+
+        let mut state = PlayerState {
+            frame: 0,
+            playing: true,
+            delay: self.delay,
+            quit: false,
+        };
+
+        while !state.quit {
+            self.draw(&state);
+
+            if state.playing {
+                match receiver.recv_timeout(state.delay) {
+                    Ok(command) => state.apply(command, self.frames.len()),
+                    Err(RecvTimeoutError::Timeout) => state.tick(self.frames.len()),
+                    Err(RecvTimeoutError::Disconnected) => break,
+                }
+            } else {
+                match receiver.recv() {
+                    Ok(command) => state.apply(command, self.frames.len()),
+                    Err(_) => break,
+                }
+            }
+        }
+    }
+
+    fn draw(&self, state: &PlayerState) {
+        let mut stdout = stdout().lock();
+        // Clear the screen and go back to the top left corner
+        write!(stdout, "\x1b[2J\x1b[H").unwrap();
+        write!(stdout, "{}", self.renderer.render(&self.frames[state.frame])).unwrap();
+        writeln!(
+            stdout,
+            "frame {}/{} - {} - {}ms (p: play/pause, n: next, b: back, +/-: speed, q: quit)",
+            state.frame + 1,
+            self.frames.len(),
+            if state.playing { "playing" } else { "paused" },
+            state.delay.as_millis(),
+        ).unwrap();
+        stdout.flush().unwrap();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::input_to_grid;
+
+    use super::{Command, PlayerState, Recorder};
+
+    #[test]
+    #[cfg(debug_assertions)]
+    fn recorder() {
+        let mut grid = input_to_grid::<u8>("12\n34").unwrap();
+        let mut recorder = Recorder::new();
+
+        recorder.record(&grid);
+        grid.rotate_cw();
+        recorder.record(&grid);
+
+        assert_eq!(recorder.len(), 2);
+        assert_eq!(format!("{:?}", recorder.frames()[1]), "31\n42\n");
+    }
+
+    #[test]
+    fn player_state() {
+        let mut state = PlayerState {
+            frame: 0,
+            playing: true,
+            delay: Duration::from_millis(200),
+            quit: false,
+        };
+
+        state.tick(3);
+        state.tick(3);
+        state.tick(3);
+        assert_eq!((state.frame, state.playing), (2, false));
+
+        state.apply(Command::Back, 3);
+        state.apply(Command::Back, 3);
+        state.apply(Command::Back, 3);
+        assert_eq!(state.frame, 0);
+
+        state.apply(Command::Next, 3);
+        assert_eq!(state.frame, 1);
+
+        state.apply(Command::Faster, 3);
+        assert_eq!(state.delay, Duration::from_millis(100));
+        state.apply(Command::Slower, 3);
+        state.apply(Command::Slower, 3);
+        assert_eq!(state.delay, Duration::from_millis(400));
+
+        state.apply(Command::Next, 3);
+        state.apply(Command::PlayPause, 3);
+        assert_eq!((state.frame, state.playing), (0, true));
+
+        state.apply(Command::Quit, 3);
+        assert!(state.quit);
+    }
+
+    #[test]
+    fn parse_command() {
+        assert_eq!(Command::parse(""), Some(Command::PlayPause));
+        assert_eq!(Command::parse(" n \n"), Some(Command::Next));
+        assert_eq!(Command::parse("q"), Some(Command::Quit));
+        assert_eq!(Command::parse("x"), None);
+    }
+}
@@ -0,0 +1,243 @@
+use std::collections::{HashMap, VecDeque};
+
+use grid::Grid;
+
+use crate::CustomGrid;
+
+/// Which cells are considered adjacent when an update spreads.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Neighborhood {
+    /// Up, down, left and right, like [`CustomGrid::iter_neighbors`]
+    Orthogonal,
+    /// The 8 surrounding cells, like [`CustomGrid::iter_diagonal_neighbors`]
+    Diagonal,
+}
+
+impl Neighborhood {
+    fn offsets(&self) -> &'static [(isize, isize)] {
+        match self {
+            Neighborhood::Orthogonal => &[(-1, 0), (0, -1), (0, 1), (1, 0)],
+            Neighborhood::Diagonal => &[(-1, -1), (-1, 0), (-1, 1), (0, -1), (0, 1), (1, -1), (1, 0), (1, 1)],
+        }
+    }
+}
+
+impl<T> CustomGrid<T> {
+    /// Positions around `(row, col)` that are inside the grid.
+    pub fn neighbor_positions(&self, row: usize, col: usize, neighborhood: Neighborhood) -> impl Iterator<Item=(usize, usize)> {
+        let (rows, cols) = (self.rows() as isize, self.cols() as isize);
+
+        neighborhood
+            .offsets()
+            .iter()
+            .map(move |(row_offset, col_offset)| (row as isize + row_offset, col as isize + col_offset))
+            .filter(move |&(row, col)| row >= 0 && col >= 0 && row < rows && col < cols)
+            .map(|(row, col)| (row as usize, col as usize))
+    }
+
+    /// Computes the next generation of the grid, every cell being updated at once from the current one.
+    ///
+    /// `rule` receives the current grid, the position of the cell and its current value.
+    pub fn next_generation<F>(&self, rule: F) -> CustomGrid<T>
+        where F: Fn(&CustomGrid<T>, (usize, usize), &T) -> T {
+        let cols = self.cols();
+        let data = (0..self.rows())
+            .flat_map(|row| (0..cols).map(move |col| (row, col)))
+            .map(|position| rule(self, position, &self.0[position]))
+            .collect();
+
+        CustomGrid(Grid::from_vec(data, cols))
+    }
+
+    /// Hits every position of `seeds` with `hit`, in order.
+    ///
+    /// Each time `hit` returns `true` the cell fires: its neighbors are queued to be hit in turn.
+    /// Returns the positions that fired, in firing order.
+    pub fn propagate<I, F>(&mut self, seeds: I, neighborhood: Neighborhood, mut hit: F) -> Vec<(usize, usize)>
+        where I: IntoIterator<Item=(usize, usize)>,
+              F: FnMut(&mut T) -> bool {
+        let mut queue: VecDeque<(usize, usize)> = seeds.into_iter().collect();
+        let mut fired = vec![];
+
+        while let Some(position) = queue.pop_front() {
+            if hit(&mut self.0[position]) {
+                fired.push(position);
+                queue.extend(self.neighbor_positions(position.0, position.1, neighborhood));
+            }
+        }
+
+        fired
+    }
+}
+
+/// A grid evolving step by step, keeping count of the steps.
+pub struct Automaton<T> {
+    grid: CustomGrid<T>,
+    steps: usize,
+}
+
+impl<T> Automaton<T> {
+    pub fn new(grid: CustomGrid<T>) -> Self {
+        Self {
+            grid,
+            steps: 0,
+        }
+    }
+
+    pub fn grid(&self) -> &CustomGrid<T> {
+        &self.grid
+    }
+
+    pub fn into_grid(self) -> CustomGrid<T> {
+        self.grid
+    }
+
+    /// Number of steps run so far.
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    /// Runs one synchronous step, see [`CustomGrid::next_generation`].
+    pub fn step<F>(&mut self, rule: F)
+        where F: Fn(&CustomGrid<T>, (usize, usize), &T) -> T {
+        self.grid = self.grid.next_generation(rule);
+        self.steps += 1;
+    }
+
+    /// Runs one step updating the grid in place, for rules that can't be expressed cell by cell
+    /// (cascades, movements...). Returns what `step` returned.
+    pub fn step_with<R, F>(&mut self, step: F) -> R
+        where F: FnOnce(&mut CustomGrid<T>) -> R {
+        let result = step(&mut self.grid);
+        self.steps += 1;
+        result
+    }
+
+    /// Runs `step` until `predicate` holds for the grid and the step result.
+    ///
+    /// Returns the step counter at that point.
+    pub fn run_until<R, F, P>(&mut self, mut step: F, mut predicate: P) -> usize
+        where F: FnMut(&mut CustomGrid<T>) -> R,
+              P: FnMut(&CustomGrid<T>, &R) -> bool {
+        loop {
+            let result = self.step_with(&mut step);
+            if predicate(&self.grid, &result) {
+                return self.steps;
+            }
+        }
+    }
+}
+
+impl<T: Clone + Eq> Automaton<T> {
+    /// Runs `step` until it doesn't change the grid anymore.
+    ///
+    /// Returns the step counter of the first step that changed nothing.
+    pub fn run_until_stable<F>(&mut self, mut step: F) -> usize
+        where F: FnMut(&mut CustomGrid<T>) {
+        loop {
+            let previous = self.grid.clone();
+            self.step_with(&mut step);
+            if self.grid == previous {
+                return self.steps;
+            }
+        }
+    }
+}
+
+impl<T: Clone + Eq + std::hash::Hash> Automaton<T> {
+    /// Runs `step` until the grid gets back to a state it already was in.
+    ///
+    /// Returns `(first, repeat)`: the step counters at which the state was first seen and seen again.
+    pub fn find_repeat<F>(&mut self, mut step: F) -> (usize, usize)
+        where F: FnMut(&mut CustomGrid<T>) {
+        let mut seen: HashMap<Vec<T>, usize> = HashMap::from([(self.state(), self.steps)]);
+
+        loop {
+            self.step_with(&mut step);
+            if let Some(&first) = seen.get(&self.state()) {
+                return (first, self.steps);
+            }
+            seen.insert(self.state(), self.steps);
+        }
+    }
+
+    fn state(&self) -> Vec<T> {
+        self.grid.iter_rows().flatten().cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{CustomGrid, input_to_grid};
+
+    use super::{Automaton, Neighborhood};
+
+    fn life(grid: &CustomGrid<char>, (row, col): (usize, usize), cell: &char) -> char {
+        let alive = grid
+            .neighbor_positions(row, col, Neighborhood::Diagonal)
+            .filter(|&position| grid[position] == '#')
+            .count();
+
+        match (cell, alive) {
+            ('#', 2 | 3) | ('.', 3) => '#',
+            _ => '.',
+        }
+    }
+
+    #[test]
+    fn synchronous_step() {
+        let mut automaton = Automaton::new(input_to_grid::<char>(".....\n..#..\n..#..\n..#..\n.....").unwrap());
+
+        automaton.step(life);
+        assert_eq!(format!("{:?}", automaton.grid()), ".....\n.....\n.###.\n.....\n.....\n");
+        assert_eq!(automaton.steps(), 1);
+    }
+
+    #[test]
+    fn find_repeat() {
+        let mut automaton = Automaton::new(input_to_grid::<char>(".....\n..#..\n..#..\n..#..\n.....").unwrap());
+
+        let (first, repeat) = automaton.find_repeat(|grid| *grid = grid.next_generation(life));
+        assert_eq!((first, repeat), (0, 2));
+    }
+
+    #[test]
+    fn run_until_stable() {
+        // Water flows down from the top row until it reaches the floor
+        let mut automaton = Automaton::new(input_to_grid::<char>("~..\n...\n...\n###").unwrap());
+
+        let steps = automaton.run_until_stable(|grid| {
+            *grid = grid.next_generation(|grid, (row, col), &c| match grid.up(row, col) {
+                Some('~') if c == '.' => '~',
+                _ => c,
+            })
+        });
+
+        assert_eq!(steps, 3);
+        assert_eq!(format!("{:?}", automaton.grid()), "~..\n~..\n~..\n###\n");
+    }
+
+    #[test]
+    fn propagate() {
+        let mut grid = input_to_grid::<u8>("800\n090\n000").unwrap();
+
+        let fired = grid.propagate([(0, 0), (1, 1)], Neighborhood::Orthogonal, |value| {
+            *value += 1;
+            *value == 10
+        });
+
+        // (1, 1) fires, which makes (0, 1) reach 1, (0, 0) stays at 9
+        assert_eq!(fired, vec![(1, 1)]);
+        assert_eq!(format!("{:?}", grid), "910\n1101\n010\n");
+
+        let mut automaton = Automaton::new(grid);
+        let steps = automaton.run_until(
+            |grid| grid.propagate([(0, 0)], Neighborhood::Orthogonal, |value| {
+                *value += 1;
+                *value == 10
+            }).len(),
+            |_, fired| *fired > 0,
+        );
+        assert_eq!(steps, 1);
+    }
+}
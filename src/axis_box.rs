@@ -0,0 +1,143 @@
+use crate::interval::Interval;
+
+/// Non-empty axis-aligned box of integer points, one [`Interval`] per axis (start included, end excluded).
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct AxisBox<const N: usize> {
+    pub axes: [Interval; N],
+}
+
+impl<const N: usize> AxisBox<N> {
+    pub fn new(axes: [Interval; N]) -> Self {
+        Self {
+            axes,
+        }
+    }
+
+    /// Box from its lowest corner (included) to its highest corner (excluded).
+    pub fn from_corners(from: [i64; N], to: [i64; N]) -> Self {
+        Self::new(std::array::from_fn(|axis| Interval::new(from[axis], to[axis])))
+    }
+
+    /// Number of points in the box.
+    pub fn volume(&self) -> u64 {
+        self.axes.iter().map(Interval::length).product()
+    }
+
+    pub fn contains(&self, point: [i64; N]) -> bool {
+        self.axes.iter().zip(point).all(|(interval, value)| interval.contains(value))
+    }
+
+    pub fn intersects(&self, other: &AxisBox<N>) -> bool {
+        self.axes.iter().zip(other.axes.iter()).all(|(a, b)| a.intersects(b))
+    }
+
+    pub fn intersection(&self, other: &AxisBox<N>) -> Option<AxisBox<N>> {
+        let mut axes = self.axes;
+        for (axis, interval) in axes.iter_mut().enumerate() {
+            *interval = interval.intersection(&other.axes[axis])?;
+        }
+        Some(Self::new(axes))
+    }
+
+    /// Disjoint boxes covering the points of `self` outside of `other`, at most `2 * N` of them.
+    pub fn subtract(&self, other: &AxisBox<N>) -> Vec<AxisBox<N>> {
+        if !self.intersects(other) {
+            return vec![*self];
+        }
+
+        // Slices off what's outside of `other` one axis at a time, narrowing down what's left
+        let mut pieces = vec![];
+        let mut rest = *self;
+        for axis in 0..N {
+            let (intersection, remainder) = rest.axes[axis].split(&other.axes[axis]);
+            for interval in remainder {
+                let mut piece = rest;
+                piece.axes[axis] = interval;
+                pieces.push(piece);
+            }
+            rest.axes[axis] = intersection.expect("boxes intersect on every axis");
+        }
+
+        pieces
+    }
+
+    /// Number of points in at least one of the boxes.
+    pub fn union_volume(boxes: &[AxisBox<N>]) -> u64 {
+        let mut disjoint: Vec<AxisBox<N>> = vec![];
+        for new_box in boxes {
+            let pieces = disjoint.iter().fold(vec![*new_box], |pieces, existing| {
+                pieces.into_iter().flat_map(|piece| piece.subtract(existing)).collect()
+            });
+            disjoint.extend(pieces);
+        }
+
+        disjoint.iter().map(AxisBox::volume).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use crate::testing::Random;
+
+    use super::AxisBox;
+
+    fn points(axis_box: &AxisBox<3>) -> HashSet<[i64; 3]> {
+        let [x, y, z] = axis_box.axes;
+        (x.from..x.to)
+            .flat_map(|i| (y.from..y.to).flat_map(move |j| (z.from..z.to).map(move |k| [i, j, k])))
+            .collect()
+    }
+
+    #[test]
+    fn intersection_and_volume() {
+        let a = AxisBox::from_corners([0, 0], [4, 3]);
+        let b = AxisBox::from_corners([2, 1], [6, 5]);
+
+        assert_eq!(a.volume(), 12);
+        assert_eq!(a.intersection(&b), Some(AxisBox::from_corners([2, 1], [4, 3])));
+        assert_eq!(a.intersection(&AxisBox::from_corners([4, 0], [5, 3])), None);
+        assert!(a.contains([3, 2]));
+        assert!(!a.contains([4, 2]));
+    }
+
+    #[test]
+    fn subtract() {
+        let outer = AxisBox::from_corners([0, 0, 0], [3, 3, 3]);
+        let center = AxisBox::from_corners([1, 1, 1], [2, 2, 2]);
+
+        let pieces = outer.subtract(&center);
+        assert_eq!(pieces.len(), 6);
+        assert_eq!(pieces.iter().map(AxisBox::volume).sum::<u64>(), 26);
+
+        let expected: HashSet<[i64; 3]> = points(&outer).difference(&points(&center)).copied().collect();
+        let mut covered = HashSet::new();
+        for piece in pieces.iter() {
+            for point in points(piece) {
+                assert!(covered.insert(point), "pieces overlap on {point:?}");
+            }
+        }
+        assert_eq!(covered, expected);
+
+        assert_eq!(center.subtract(&outer), vec![]);
+        assert_eq!(outer.subtract(&AxisBox::from_corners([5, 5, 5], [6, 6, 6])), vec![outer]);
+    }
+
+    #[test]
+    fn union_volume_matches_brute_force() {
+        let mut random = Random::new(42);
+
+        for _ in 0..200 {
+            let boxes: Vec<AxisBox<3>> = (0..random.below(6))
+                .map(|_| {
+                    let from = [random.range(0..8), random.range(0..8), random.range(0..8)];
+                    AxisBox::from_corners(from, from.map(|value| value + random.range(1..6)))
+                })
+                .collect();
+
+            let brute_force: HashSet<[i64; 3]> = boxes.iter().flat_map(points).collect();
+            assert_eq!(AxisBox::union_volume(&boxes), brute_force.len() as u64);
+        }
+    }
+}
@@ -1,10 +1,11 @@
+use advent_of_code::parse::unwrap_parsed;
 use advent_of_code::{input_to_list, read_input, should_submit, submit};
 
 const DAY: u8 = 1;
 const YEAR: u16 = 2021;
 
 pub fn part_one(input: &str) -> Option<u32> {
-    let list: Vec<u32> = input_to_list(input).unwrap();
+    let list: Vec<u32> = unwrap_parsed(input_to_list(input));
 
     let mut number_of_increases: u32 = 0;
     let mut previous: Option<u32> = None;
@@ -24,7 +25,7 @@ pub fn part_one(input: &str) -> Option<u32> {
 
 
 pub fn part_two(input: &str) -> Option<u32> {
-    let list: Vec<u32> = input_to_list(input).unwrap();
+    let list: Vec<u32> = unwrap_parsed(input_to_list(input));
 
     let mut number_of_increases: u32 = 0;
     let mut previous: Option<u32> = None;
@@ -1,10 +1,11 @@
+use advent_of_code::parse::unwrap_parsed;
 use advent_of_code::{input_to_list, read_input, should_submit, submit};
 
 const DAY: u8 = 2;
 const YEAR: u16 = 2021;
 
 pub fn part_one(input: &str) -> Option<i32> {
-    let list: Vec<String> = input_to_list(input).unwrap();
+    let list: Vec<String> = unwrap_parsed(input_to_list(input));
     let mut position: i32 = 0;
     let mut depth: i32 = 0;
 
@@ -28,7 +29,7 @@ pub fn part_one(input: &str) -> Option<i32> {
 
 
 pub fn part_two(input: &str) -> Option<i32> {
-    let list: Vec<String> = input_to_list(input).unwrap();
+    let list: Vec<String> = unwrap_parsed(input_to_list(input));
     let mut position: i32 = 0;
     let mut depth: i32 = 0;
     let mut aim: i32 = 0;
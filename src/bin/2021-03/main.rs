@@ -1,88 +1,51 @@
+use advent_of_code::parse::unwrap_parsed;
 use advent_of_code::{input_to_list, read_input, should_submit, submit};
+use advent_of_code::bitset::{bit_counts, Bits};
 
 const DAY: u8 = 3;
 const YEAR: u16 = 2021;
 
-fn get_gamma_epsilon(list: &Vec<u32>, width: usize) -> (u32, u32) {
-    let bit_count: Vec<u32> = list
-        .iter()
-        .fold(vec![0; width], |count, value| {
-            count.into_iter().enumerate().map(
-                |(bit, bit_count)| bit_count + ((value & 1 << bit) >> bit)
-            ).collect()
-        });
-
-    let gamma: u32 = bit_count
-        .into_iter()
-        .enumerate()
-        .map(|(bit, count)| {
-            ((count * 2 >= list.len() as u32) as u32) << bit
-        })
-        .sum();
-
-    let epsion_mask = (1 << width) - 1;
-    let epsilon = gamma ^ epsion_mask;
-
-    (gamma, epsilon)
+fn get_gamma_epsilon(list: &[Bits]) -> (Bits, Bits) {
+    let mut gamma = Bits::new(list[0].width());
+    for (bit, count) in bit_counts(list).into_iter().enumerate() {
+        if count * 2 >= list.len() {
+            gamma.insert(bit as u32);
+        }
+    }
+
+    (gamma, !gamma)
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
-    let list: Vec<String> = input_to_list(input).unwrap();
-
-    let width = list[0].len();
-
-    let list = list
-        .iter()
-        .map(|value| u32::from_str_radix(value, 2).unwrap())
-        .collect();
+    let list: Vec<Bits> = unwrap_parsed(input_to_list(input));
 
-    let (gamma, epsilon) = get_gamma_epsilon(&list, width);
+    let (gamma, epsilon) = get_gamma_epsilon(&list);
 
-    Some(epsilon * gamma)
+    Some((epsilon.word() * gamma.word()) as u32)
 }
 
+// Keeps the values having the bit of `criteria` (gamma or epsilon) from the highest bit down, until one is left
+fn rating(mut list: Vec<Bits>, criteria: impl Fn(&[Bits]) -> Bits) -> u64 {
+    for i in (0..list[0].width()).rev() {
+        let bit = criteria(&list).contains(i);
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let list: Vec<String> = input_to_list(input).unwrap();
-    let width = list[0].len();
-
-    let list: Vec<u32> = list
-        .iter()
-        .map(|value| u32::from_str_radix(value, 2).unwrap())
-        .collect();
-
-    let mut oxygen_list: Vec<u32> = list.clone();
-
-    for i in (0..width).rev() {
-        // It's a bit overkill to compute gamma for all bits, we only need the informations for the current (i th) bit.
-        let (gamma, _) = get_gamma_epsilon(&oxygen_list, width);
-        let gamma_bit = (gamma & (1 << i)) >> i;
+        list.retain(|value| value.contains(i) == bit);
 
-        oxygen_list = oxygen_list.clone().into_iter().filter(|value| (*value & (1 << i)) >> i == gamma_bit).collect();
-
-        if oxygen_list.len() == 1 {
+        if list.len() == 1 {
             break;
         }
     }
 
-    let oxygen = oxygen_list.last().copied().unwrap();
-
-    let mut co2_list: Vec<u32> = list;
-
-    for i in (0..width).rev() {
-        let (_, epsilon) = get_gamma_epsilon(&co2_list, width);
-        let epsilon_bit = (epsilon & (1 << i)) >> i;
-
-        co2_list = co2_list.clone().into_iter().filter(|value| (*value & (1 << i)) >> i == epsilon_bit).collect();
+    list.last().unwrap().word()
+}
 
-        if co2_list.len() == 1 {
-            break;
-        }
-    }
+pub fn part_two(input: &str) -> Option<u32> {
+    let list: Vec<Bits> = unwrap_parsed(input_to_list(input));
 
-    let co2 = co2_list.last().copied().unwrap();
+    let oxygen = rating(list.clone(), |list| get_gamma_epsilon(list).0);
+    let co2 = rating(list, |list| get_gamma_epsilon(list).1);
 
-    Some(oxygen * co2)
+    Some((oxygen * co2) as u32)
 }
 
 fn main() {
@@ -1,16 +1,16 @@
-use advent_of_code::{read_input, should_submit, submit};
+use advent_of_code::{read_input, sections, should_submit, submit};
 
 const DAY: u8 = 4;
 const YEAR: u16 = 2021;
 
 
 fn parse_input(input: &str) -> (Vec<u8>, Vec<Vec<u8>>) {
-    let (numbers, boards) = input.split_once("\n\n").unwrap();
+    let mut sections = sections(input);
 
-    let numbers: Vec<u8> = numbers.split(',').map(|n| n.parse().unwrap()).collect();
+    let numbers: Vec<u8> = sections.next().unwrap().split(',').map(|n| n.parse().unwrap()).collect();
 
-    let boards = boards.split("\n\n").map(
-        |board| board.replace('\n', " ").split_whitespace().map(|n| n.parse().unwrap()).collect()
+    let boards = sections.map(
+        |board| board.split_whitespace().map(|n| n.parse().unwrap()).collect()
     ).collect();
 
     (numbers, boards)
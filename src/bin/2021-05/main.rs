@@ -1,47 +1,31 @@
 use std::cmp::{max, min};
-use std::collections::HashMap;
 use std::str::FromStr;
-use advent_of_code::{read_input, should_submit, submit};
-use nom::{
-    IResult,
-    bytes::complete::{tag, take_while},
-    character::complete::char,
-    combinator::{map, map_res},
-    sequence::{tuple, separated_pair},
-};
+use advent_of_code::{read_input, should_submit, SparseGrid, submit};
+use advent_of_code::parse::{coordinates, parse_all};
+use nom::{bytes::complete::tag, sequence::separated_pair};
 use itertools::Itertools;
 
 const DAY: u8 = 5;
 const YEAR: u16 = 2021;
 
-fn uint<T>(input: &str) -> IResult<&str, T> where T: FromStr {
-    map_res(take_while(|c: char| c.is_ascii_digit()), |s: &str| s.parse::<T>())(input)
-}
-
-
 fn parse_line<T>(input: &str) -> (T, T, T, T) where T: FromStr {
-    let tuple_parser = tuple((separated_pair(uint, char(','), uint), tag(" -> "), separated_pair(uint, char(','), uint)));
-    let mut result_parser = map(tuple_parser, |((a, b), _, (c, d))| (a, b, c, d));
-
-    let (_, test) = result_parser(input).unwrap();
-    test
+    let ((a, b), (c, d)) = parse_all(separated_pair(coordinates, tag(" -> "), coordinates), input);
+    (a, b, c, d)
 }
 
 
 pub fn part_one(input: &str) -> Option<usize> {
     let count = input.lines()
-        .map(parse_line::<u16>)
+        .map(parse_line::<isize>)
         .filter(|(x1, y1, x2, y2)| x1 == x2 || y1 == y2) // Filter only horizontal & vertical lines
-        .fold(HashMap::new(), |mut counts, (x1, y1, x2, y2)| {
-            for point in (min(x1, x2)..=max(x1, x2)).cartesian_product(min(y1, y2)..=max(y1, y2)) {
-                *counts.entry(point).or_insert(0) += 1;
+        .fold(SparseGrid::new(), |mut counts, (x1, y1, x2, y2)| {
+            for (x, y) in (min(x1, x2)..=max(x1, x2)).cartesian_product(min(y1, y2)..=max(y1, y2)) {
+                *counts.get_mut_or_insert(y, x, 0) += 1;
             }
             counts
         })
-        .into_iter()
-        .filter(|((_, _), count)| {
-            *count >= 2
-        })
+        .iter()
+        .filter(|&&count| count >= 2)
         .count();
 
     Some(count)
@@ -50,26 +34,24 @@ pub fn part_one(input: &str) -> Option<usize> {
 
 pub fn part_two(input: &str) -> Option<usize> {
     let count = input.lines()
-        .map(parse_line::<i32>)
+        .map(parse_line::<isize>)
         .filter(|(x1, y1, x2, y2)| x1 == x2 || y1 == y2 || x1.abs_diff(*x2) == y1.abs_diff(*y2)) // Filter only horizontal, vertical and 45° lines
-        .fold(HashMap::new(), |mut counts, (x1, y1, x2, y2)| {
+        .fold(SparseGrid::new(), |mut counts, (x1, y1, x2, y2)| {
             let dx = (x2 - x1).signum();
             let dy = (y2 - y1).signum();
 
             let (mut x, mut y) = (x1, y1);
 
             while (x, y) != (x2 + dx, y2 + dy) {
-                *counts.entry((x, y)).or_insert(0) += 1;
+                *counts.get_mut_or_insert(y, x, 0) += 1;
                 x += dx;
                 y += dy;
             }
 
             counts
         })
-        .into_iter()
-        .filter(|((_, _), count)| {
-            *count >= 2
-        })
+        .iter()
+        .filter(|&&count| count >= 2)
         .count();
 
     Some(count)
@@ -1,32 +1,28 @@
-use itertools::Itertools;
-use nom::{IResult};
-use nom::bytes::complete::{tag, take_while};
-use nom::character::complete::char;
-use nom::combinator::{map_res, opt};
-use nom::multi::{many_m_n};
-use nom::sequence::{separated_pair, terminated};
+use nom::bytes::complete::tag;
+use nom::character::complete::alpha1;
+use nom::combinator::map;
+use nom::sequence::separated_pair;
 
 use advent_of_code::{read_input, should_submit, submit};
+use advent_of_code::bitset::Bits;
+use advent_of_code::constraint::{Problem, Var};
+use advent_of_code::parse::{parse_all, space_list};
 
 const DAY: u8 = 8;
 const YEAR: u16 = 2021;
 
-pub fn parse_line(input: &str) -> ([String; 10], [String; 4]) {
-    let left_parser = many_m_n(0, 10, terminated(map_res(take_while(|c: char| c.is_alphabetic()), |s: &str| s.parse()), opt(char(' '))));
-    let right_parser = many_m_n(0, 4, terminated(map_res(take_while(|c: char| c.is_alphabetic()), |s: &str| s.parse()), opt(char(' '))));
-    let mut parser = separated_pair(left_parser, tag("| "), right_parser);
-
-    let result: IResult<&str, (Vec<String>, Vec<String>)> = parser(input);
-    let (_, parsed_line) = result.unwrap();
+pub fn parse_line(input: &str) -> ([Bits; 10], [Bits; 4]) {
+    let words = || space_list(map(alpha1, |word| Bits::from_letters(word).unwrap()));
+    let (patterns, outputs) = parse_all(separated_pair(words(), tag(" | "), words()), input);
 
     (
-        parsed_line.0.try_into().unwrap(),
-        parsed_line.1.try_into().unwrap(),
+        patterns.try_into().unwrap(),
+        outputs.try_into().unwrap(),
     )
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
-    let parsed: Vec<([String; 10], [String; 4])> = input.lines().map(parse_line).collect();
+    let parsed: Vec<([Bits; 10], [Bits; 4])> = input.lines().map(parse_line).collect();
 
     let count = parsed.into_iter().fold(0, |acc, (_, outputs)| {
         acc + outputs.iter().filter(|digits| [
@@ -41,88 +37,39 @@ pub fn part_one(input: &str) -> Option<u32> {
     Some(count as u32)
 }
 
-pub fn compute_line_value(line: ([String; 10], [String; 4])) -> u32 {
-    let one = line.0.iter().find(|word| word.len() == 2).unwrap();
-    let seven = line.0.iter().find(|word| word.len() == 3).unwrap();
-    let eight = line.0.iter().find(|word| word.len() == 7).unwrap();
-
-    println!("One:   {one}");
-    println!("Seven: {seven}");
-    println!("Eight: {eight}");
-
-    let top = seven.chars().find(|c| !one.contains(*c)).unwrap();
-
-    println!("top: {top}");
-
-    let digits_with_6_bars: Vec<&String> = line.0.iter().filter(|word| word.len() == 6).collect();
-
-    let six = *digits_with_6_bars.iter().find(|x| !one.chars().all(|c| x.contains(c))).unwrap();
-
-    println!("Six: {six}");
-
-    let bottom_right = one.chars().find(|c| six.contains(*c)).unwrap();
-    let top_right = one.chars().find(|c| !six.contains(*c)).unwrap();
-
-    println!("bottom_right: {bottom_right}");
-    println!("top_right: {top_right}");
-
-    let three = line.0.iter().filter(|word| {
-        word.chars().filter(|c| [top, top_right, bottom_right].contains(c)).count() == 3
-    }).find(|word| word.len() == 5).unwrap();
-
-    println!("Three: {three}");
+// Segments lit by each digit on a well wired display
+const DIGITS: [&str; 10] = ["abcefg", "cf", "acdeg", "acdfg", "bcdf", "abdfg", "abdefg", "acf", "abcdefg", "abcdfg"];
 
-    let nine = *digits_with_6_bars.iter().find(|x| three.chars().all(|c| x.contains(c))).unwrap();
+pub fn compute_line_value((patterns, outputs): ([Bits; 10], [Bits; 4])) -> u32 {
+    let digits = DIGITS.map(|segments| Bits::from_letters(segments).unwrap());
 
-    println!("Nine: {nine}");
+    // Each pattern is a digit with as many segments, and two patterns share as many segments as their digits
+    let mut problem = Problem::new();
+    let variables: Vec<Var> = patterns
+        .iter()
+        .map(|pattern| problem.variable((0..10).filter(|&digit| digits[digit].len() == pattern.len())))
+        .collect();
+    problem.all_different(&variables);
 
-    let zero = *digits_with_6_bars.iter().find(|word| ![six, nine].contains(word)).unwrap();
-
-    println!("Zero: {zero}");
-
-    let two_and_five: Vec<&String> = line.0.iter().filter(|word| word.len() == 5 && !word.eq(&three)).collect();
-
-    let two = *two_and_five.iter().find(|word| word.chars().contains(&top_right)).unwrap();
-    let five = *two_and_five.iter().find(|word| !word.chars().contains(&top_right)).unwrap();
-
-    println!("Two: {two}");
-    println!("Five: {five}");
-
-    let four = line.0.iter().find(|word| word.len() == 4).unwrap();
-
-    println!("Four: {four}");
-
-    let line_value = line.1.iter().enumerate().fold(0, |acc, (i, word)| {
-        let exponent: u32 = 3u32 - (i as u32);
-        let multiplicator = 10u32.pow(exponent);
-
-        let value = word.chars().sorted().collect::<String>();
-        acc + multiplicator * match value {
-            _ if value.eq(&zero.chars().sorted().collect::<String>()) => 0,
-            _ if value.eq(&one.chars().sorted().collect::<String>()) => 1,
-            _ if value.eq(&two.chars().sorted().collect::<String>()) => 2,
-            _ if value.eq(&three.chars().sorted().collect::<String>()) => 3,
-            _ if value.eq(&four.chars().sorted().collect::<String>()) => 4,
-            _ if value.eq(&five.chars().sorted().collect::<String>()) => 5,
-            _ if value.eq(&six.chars().sorted().collect::<String>()) => 6,
-            _ if value.eq(&seven.chars().sorted().collect::<String>()) => 7,
-            _ if value.eq(&eight.chars().sorted().collect::<String>()) => 8,
-            _ if value.eq(&nine.chars().sorted().collect::<String>()) => 9,
-            _ => unreachable!()
+    for i in 0..10 {
+        for j in i + 1..10 {
+            let shared = (patterns[i] & patterns[j]).len();
+            problem.relation(variables[i], variables[j], move |&a, &b| (digits[a] & digits[b]).len() == shared);
         }
-    });
+    }
 
-    println!("Line value: {line_value}");
+    let solution = problem.solve().unwrap();
 
-    line_value
+    outputs.iter().fold(0, |acc, output| {
+        let pattern = patterns.iter().position(|pattern| pattern == output).unwrap();
+        acc * 10 + *solution[pattern] as u32
+    })
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    let parsed: Vec<([String; 10], [String; 4])> = input.lines().map(parse_line).collect();
-
-    let total_sum: u32 = parsed.into_iter().map(|line| compute_line_value(line)).sum();
+    let parsed: Vec<([Bits; 10], [Bits; 4])> = input.lines().map(parse_line).collect();
 
-    println!("Total sum: {}", total_sum);
+    let total_sum: u32 = parsed.into_iter().map(compute_line_value).sum();
 
     Some(total_sum)
 }
@@ -1,3 +1,4 @@
+use advent_of_code::parse::unwrap_parsed;
 use advent_of_code::{CustomGrid, input_to_grid, read_input, should_submit, submit};
 
 const DAY: u8 = 9;
@@ -27,7 +28,7 @@ fn lowest_points(grid: &CustomGrid<u8>) -> Vec<(usize, usize)> {
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
-    let grid: CustomGrid<u8> = input_to_grid(input).unwrap();
+    let grid: CustomGrid<u8> = unwrap_parsed(input_to_grid(input));
 
     let lowest_points = lowest_points(&grid);
 
@@ -37,7 +38,7 @@ pub fn part_one(input: &str) -> Option<u32> {
 }
 
 fn basin(grid: &mut CustomGrid<u8>, x: usize, y: usize) -> usize {
-    grid[y][x] = 9;
+    grid[(y, x)] = 9;
 
     NEXT
         .iter()
@@ -52,7 +53,7 @@ fn basin(grid: &mut CustomGrid<u8>, x: usize, y: usize) -> usize {
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    let mut grid: CustomGrid<u8> = input_to_grid(input).unwrap();
+    let mut grid: CustomGrid<u8> = unwrap_parsed(input_to_grid(input));
 
     let lowest_points = lowest_points(&grid);
 
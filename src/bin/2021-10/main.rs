@@ -1,5 +1,6 @@
 use std::collections::VecDeque;
 use itertools::Itertools;
+use advent_of_code::parse::unwrap_parsed;
 use advent_of_code::{input_to_list, read_input, should_submit, submit};
 
 const DAY: u8 = 10;
@@ -36,7 +37,7 @@ pub fn score_part_two(c: char) -> u32 {
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
-    let list: Vec<String> = input_to_list(input).unwrap();
+    let list: Vec<String> = unwrap_parsed(input_to_list(input));
 
     let total = list.iter().fold(0, |acc, line| {
         let mut stack: Vec<char> = Vec::new();
@@ -62,7 +63,7 @@ pub fn part_one(input: &str) -> Option<u32> {
 
 
 pub fn part_two(input: &str) -> Option<u32> {
-    let list: Vec<String> = input_to_list(input).unwrap();
+    let list: Vec<String> = unwrap_parsed(input_to_list(input));
 
     let total: Vec<u64> = list.iter().map(|line| {
         let mut stack: Vec<char> = Vec::new();
@@ -1,73 +1,41 @@
-use advent_of_code::{input_to_grid, read_input, should_submit, submit};
+use advent_of_code::parse::unwrap_parsed;
+use advent_of_code::{Automaton, CustomGrid, input_to_grid, Neighborhood, read_input, should_submit, submit};
 
 const DAY: u8 = 11;
 const YEAR: u16 = 2021;
 
-const NEXT: [(isize, isize); 8] = [(-1, -1), (-1, 0), (-1, 1), (0, -1), (0, 1), (1, -1), (1, 0), (1, 1)];
-
-fn increment(grid: &mut grid::Grid<u8>, x: usize, y: usize) {
-    if grid.get(y, x).is_none() {
-        return;
+// Increments every octopus, cascading the flashes, then resets the ones that flashed.
+// Returns the number of flashes.
+fn step(grid: &mut CustomGrid<u8>) -> u32 {
+    let octopuses = grid.positions(|_| true);
+    let flashed = grid.propagate(octopuses, Neighborhood::Diagonal, |energy| {
+        *energy += 1;
+        *energy == 10
+    });
+
+    for &position in flashed.iter() {
+        grid[position] = 0;
     }
-    grid[y][x] += 1;
 
-    if grid[y][x] == 10 {
-        for (xx, yy) in NEXT {
-            increment(grid, (x as isize + xx) as usize, (y as isize + yy) as usize)
-        }
-    }
+    flashed.len() as u32
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
-    let mut grid = input_to_grid::<u8>(input).unwrap();
+    let mut automaton = Automaton::new(unwrap_parsed(input_to_grid::<u8>(input)));
     let n_steps = 100u8;
-    let mut n_flashes = 0u32;
-
-    for _ in 0..n_steps {
-        for x in 0..grid.cols() {
-            for y in 0..grid.rows() {
-                increment(&mut grid, x, y)
-            }
-        }
-        for x in 0..grid.cols() {
-            for y in 0..grid.rows() {
-                if grid[y][x] >= 10 {
-                    grid[y][x] = 0;
-                    n_flashes += 1;
-                }
-            }
-        }
-    }
 
-    Some(n_flashes)
+    Some((0..n_steps).map(|_| automaton.step_with(step)).sum())
 }
 
 
 pub fn part_two(input: &str) -> Option<u32> {
-    let mut grid = input_to_grid::<u8>(input).unwrap();
-    let mut i= 0;
-
-    loop {
-        i += 1;
-        for x in 0..grid.cols() {
-            for y in 0..grid.rows() {
-                increment(&mut grid, x, y)
-            }
-        }
-        let mut n_flashes = 0u32;
-        for x in 0..grid.cols() {
-            for y in 0..grid.rows() {
-                if grid[y][x] >= 10 {
-                    grid[y][x] = 0;
-                    n_flashes += 1;
-                }
-            }
-        }
+    let mut automaton = Automaton::new(unwrap_parsed(input_to_grid::<u8>(input)));
 
-        if n_flashes == (grid.cols() * grid.rows()) as u32 {
-            return Some(i)
-        }
-    }
+    let i = automaton.run_until(step, |grid, &n_flashes| {
+        n_flashes == (grid.cols() * grid.rows()) as u32
+    });
+
+    Some(i as u32)
 }
 
 fn main() {
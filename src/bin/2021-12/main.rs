@@ -1,143 +1,34 @@
-use std::collections::HashMap;
-
-use advent_of_code::{input_to_list, read_input, should_submit, submit};
+use advent_of_code::parse::unwrap_parsed;
+use advent_of_code::{read_input, should_submit, submit};
+use advent_of_code::graph::Graph;
+use advent_of_code::paths::{count_paths, VisitPolicy};
 
 const DAY: u8 = 12;
 const YEAR: u16 = 2021;
-#[derive(PartialEq)]
-enum NodeType {
-    Start,
-    End,
-    Big,
-    Small
-}
-
-struct Node {
-    name: String,
-    neighbors: Vec<String>,
-    node_type: NodeType
-}
 
-fn is_uppercase(input: &str) -> bool {
-    input.chars().all(|c| c.is_ascii_uppercase())
+fn is_small_cave(name: &str) -> bool {
+    name.chars().all(|c| c.is_ascii_lowercase())
 }
 
-fn parse_graph(input: &str) -> HashMap<String, Node> {
-    let list: Vec<String> = input_to_list(input).unwrap();
-    let mut nodes: HashMap<String, Node> = HashMap::new();
-
-    for line in list {
-        let names: Vec<String> = line.split('-').map(|n| n.to_string()).collect();
-
-        let name1 = &names[0];
-        let name2 = &names[1];
-
-        if let Some(node) = nodes.get_mut(name1) {
-            node.neighbors.push(name2.clone())
-        } else {
-            nodes.insert(name1.clone(), Node{
-                name: name1.clone(),
-                neighbors: vec![name2.clone()],
-                node_type: match name1.as_str() {
-                    "start" => NodeType::Start,
-                    "end" => NodeType::End,
-                    x if is_uppercase(x) => NodeType::Big,
-                    _ => NodeType::Small
-                },
-            });
-        }
-
-
-        if let Some(node) = nodes.get_mut(name2) {
-            node.neighbors.push(name1.clone())
-        } else {
-            nodes.insert(name2.clone(), Node{
-                name: name2.clone(),
-                neighbors: vec![name1.clone()],
-                node_type: match name2.as_str() {
-                    "start" => NodeType::Start,
-                    "end" => NodeType::End,
-                    x if is_uppercase(x) => NodeType::Big,
-                    _ => NodeType::Small
-                },
-            });
-        }
-    }
-
-    nodes
-}
-
-fn visit(nodes: &HashMap<String, Node>, visited_paths: &mut Vec<Vec<String>>, current_node_name: &str, current_path: &mut Vec<String>) {
-    let current_node = nodes.get(current_node_name).unwrap();
-
-    if current_node.node_type != NodeType::Big && current_path.contains(&current_node_name.to_owned()) {
-        return
-    }
-
-    current_path.push(current_node.name.clone());
-
-
-    if current_node.node_type == NodeType::End {
-        visited_paths.push(current_path.clone());
-        return
-    }
-
-    for neighbor_name in current_node.neighbors.iter() {
-        visit(nodes, visited_paths, neighbor_name, &mut current_path.clone());
-    }
+fn parse_graph(input: &str) -> Graph {
+    unwrap_parsed(Graph::parse_pairs(input, "-", false))
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
-    let nodes = parse_graph(input);
+    let graph = parse_graph(input);
+    let policy = VisitPolicy::at_most_once(&graph, is_small_cave);
 
-    let mut paths: Vec<Vec<String>> = vec![];
-
-    visit(&nodes, &mut paths, "start", &mut vec![]);
-
-    Some(paths.len() as u32)
-}
-
-fn visit_part_two(nodes: &HashMap<String, Node>, visited_paths: &mut Vec<Vec<String>>, current_node_name: &str, current_path: &mut Vec<String>, visited_small_cave_twice: bool) {
-    let current_node = nodes.get(current_node_name).unwrap();
-    let mut visited_small_cave_twice= visited_small_cave_twice;
-
-    if current_path.contains(&current_node_name.to_owned()) {
-        match current_node.node_type {
-            NodeType::Start => {
-                return
-            }
-            NodeType::Small => {
-                if visited_small_cave_twice {
-                    return
-                }
-                visited_small_cave_twice = true
-
-            }
-            _ => {}
-
-        }
-    };
-
-    current_path.push(current_node.name.clone());
-
-    if current_node.node_type == NodeType::End {
-        visited_paths.push(current_path.clone());
-        return
-    }
-
-    for neighbor_name in current_node.neighbors.iter() {
-        visit_part_two(nodes, visited_paths, neighbor_name, &mut current_path.clone(), visited_small_cave_twice);
-    }
+    Some(count_paths(&graph, graph.id("start")?, graph.id("end")?, &policy) as u32)
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    let nodes = parse_graph(input);
-
-    let mut paths: Vec<Vec<String>> = vec![];
-
-    visit_part_two(&nodes, &mut paths, "start", &mut vec![], false);
+    let graph = parse_graph(input);
+    let start = graph.id("start")?;
+    let policy = VisitPolicy::at_most_once(&graph, is_small_cave)
+        .allow_one_revisit()
+        .protect(start);
 
-    Some(paths.len() as u32)
+    Some(count_paths(&graph, start, graph.id("end")?, &policy) as u32)
 }
 
 fn main() {
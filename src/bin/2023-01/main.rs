@@ -1,65 +1,40 @@
-use itertools::Itertools;
-use nom::FindSubstring;
-
 use advent_of_code::{read_input, should_submit, submit};
+use advent_of_code::bytes::{byte_lines, digit};
 use std::time::Instant;
 
 const DAY: u8 = 1;
 const YEAR: u16 = 2023;
 
+fn calibration_value(mut digits: impl DoubleEndedIterator<Item=u8>) -> u32 {
+    let first = digits.next().unwrap();
+    let last = digits.next_back().unwrap_or(first);
+    (first * 10 + last) as u32
+}
+
 pub fn part_one(input: &str) -> Option<u32> {
-    let response = input.lines().into_iter().map(|line| {
-        let numeric_chars: Vec<_> = line.chars().filter(|x| x.is_numeric()).collect();
-        format!("{}{}",
-                numeric_chars.first().unwrap(),
-                numeric_chars.last().unwrap()
-        )
-            .parse::<u32>()
-            .unwrap()
-    }).sum();
+    let response = byte_lines(input.as_bytes())
+        .map(|line| calibration_value(line.iter().filter_map(|&byte| digit(byte))))
+        .sum();
     Some(response)
 }
 
-const INT_STRINGS: [&str; 9] = ["one", "two", "three", "four", "five", "six", "seven", "eight", "nine"];
-
-pub fn part_two(input: &str) -> Option<u32> {
-    let response = input.lines().into_iter().map(|mut line| {
-        let mut replaced = true;
-
-        // Absolute overkill solution
-        // At first, i thought nineight should be understood as 9ight
-        // But actually, it is expected to be 98
-        // Simple adjustment is to replace 9 -> 9e (keeping the last char to be reused later)
-        while replaced {
-            let mut int_substrings_index: Vec<_> = INT_STRINGS
-                .iter()
-                .enumerate()
-                .filter_map(|(index, &int_string)|
-                    line
-                        .find_substring(int_string)
-                        .map(|i| (i, index)))
-                .sorted_by(|(a, _), (b, _)| Ord::cmp(b, a))
-                .collect();
+const INT_STRINGS: [&[u8]; 9] = [b"one", b"two", b"three", b"four", b"five", b"six", b"seven", b"eight", b"nine"];
 
-            if let Some((_, i)) = int_substrings_index.pop() {
-                // The trick
-                let last_char = INT_STRINGS[i].chars().last().unwrap();
-
-                line = &*line.replacen(INT_STRINGS[i], &*format!("{}{}", i + 1, last_char), 1).leak();
-                replaced = true
-            } else {
-                replaced = false
-            }
-        }
+// Digit written at the start of `bytes`, spelled or not
+fn spelled_digit(bytes: &[u8]) -> Option<u8> {
+    digit(bytes[0]).or_else(|| {
+        INT_STRINGS
+            .iter()
+            .position(|int_string| bytes.starts_with(int_string))
+            .map(|i| i as u8 + 1)
+    })
+}
 
-        let numeric_chars: Vec<_> = line.chars().filter(|x| x.is_numeric()).collect();
-        format!("{}{}",
-                numeric_chars.first().unwrap(),
-                numeric_chars.last().unwrap()
-        )
-            .parse::<u32>()
-            .unwrap()
-    }).sum();
+pub fn part_two(input: &str) -> Option<u32> {
+    // Spelled digits can overlap ("nineight" is 98), so every position is looked at
+    let response = byte_lines(input.as_bytes())
+        .map(|line| calibration_value((0..line.len()).filter_map(|i| spelled_digit(&line[i..]))))
+        .sum();
     Some(response)
 }
 
@@ -1,63 +1,40 @@
-use advent_of_code::{read_input, should_submit, submit};
+use advent_of_code::parse::unwrap_parsed;
+use advent_of_code::{AocParse, read_input, should_submit, submit};
 
 const DAY: u8 = 2;
 const YEAR: u16 = 2023;
 
-struct Draw {
-    red: u32,
-    green: u32,
-    blue: u32,
+#[derive(AocParse, Copy, Clone, PartialEq)]
+enum Color {
+    Red,
+    Green,
+    Blue,
 }
 
-struct Game {
-    id: u32,
-    draws: Vec<Draw>,
+#[derive(AocParse)]
+#[aoc(format = "{count} {color}")]
+struct Cubes {
+    count: u32,
+    color: Color,
 }
 
-fn parse_draw(draw_str: &str) -> Draw {
-    let mut draw = Draw {
-        green: 0,
-        blue: 0,
-        red: 0,
-    };
-    for color_str in draw_str.split(',') {
-        let mut parts = color_str.trim().split(' ');
-        let n: u32 = parts.next().unwrap().parse().unwrap();
-        let color: &str = parts.next().unwrap();
-
-        match color {
-            "red" => {
-                draw.red = n
-            }
-            "blue" => {
-                draw.blue = n
-            }
-            "green" => {
-                draw.green = n
-            }
-            _ => panic!()
-        }
-    }
-
-    draw
+#[derive(AocParse)]
+#[aoc(format = "{cubes:sep(',')}")]
+struct Draw {
+    cubes: Vec<Cubes>,
 }
 
-fn parse_draws(draws_str: &str) -> Vec<Draw> {
-    draws_str.split(';')
-        .into_iter()
-        .map(|draw_str| parse_draw(draw_str))
-        .collect()
+impl Draw {
+    fn count(&self, color: Color) -> u32 {
+        self.cubes.iter().filter(|cubes| cubes.color == color).map(|cubes| cubes.count).sum()
+    }
 }
 
-fn parse_game(game_str: &str) -> Game {
-    let mut parts = game_str.splitn(2, ':');
-    let id: u32 = parts.next().unwrap().split(' ').last().unwrap().parse().unwrap();
-    let draws = parse_draws(parts.next().unwrap());
-
-    Game {
-        id,
-        draws,
-    }
+#[derive(AocParse)]
+#[aoc(format = "Game {id}: {draws:sep(';')}")]
+struct Game {
+    id: u32,
+    draws: Vec<Draw>,
 }
 
 
@@ -65,13 +42,12 @@ pub fn part_one(input: &str) -> Option<u32> {
     Some(
         input
             .lines()
-            .into_iter()
-            .map(|line| parse_game(line))
+            .map(|line| unwrap_parsed(line.parse::<Game>()))
             .filter(|game: &Game| {
                 game.draws.iter().all(|draw: &Draw| {
-                    draw.blue <= 14
-                        && draw.green <= 13
-                        && draw.red <= 12
+                    draw.count(Color::Blue) <= 14
+                        && draw.count(Color::Green) <= 13
+                        && draw.count(Color::Red) <= 12
                 })
             })
             .map(|game: Game| game.id)
@@ -84,12 +60,11 @@ pub fn part_two(input: &str) -> Option<u32> {
     Some(
         input
             .lines()
-            .into_iter()
-            .map(|line| parse_game(line))
+            .map(|line| unwrap_parsed(line.parse::<Game>()))
             .map(|game: Game| {
-                let max_red = game.draws.iter().map(|draw: &Draw| draw.red).max().unwrap_or(0);
-                let max_blue = game.draws.iter().map(|draw: &Draw| draw.blue).max().unwrap_or(0);
-                let max_green = game.draws.iter().map(|draw: &Draw| draw.green).max().unwrap_or(0);
+                let max_red = game.draws.iter().map(|draw: &Draw| draw.count(Color::Red)).max().unwrap_or(0);
+                let max_blue = game.draws.iter().map(|draw: &Draw| draw.count(Color::Blue)).max().unwrap_or(0);
+                let max_green = game.draws.iter().map(|draw: &Draw| draw.count(Color::Green)).max().unwrap_or(0);
 
                 max_blue * max_red * max_green
             })
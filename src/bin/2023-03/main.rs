@@ -2,13 +2,14 @@ use std::collections::BTreeMap;
 use std::iter::once;
 use itertools::Itertools;
 
+use advent_of_code::parse::unwrap_parsed;
 use advent_of_code::{CustomGrid, input_to_grid, read_input, should_submit, submit};
 
 const DAY: u8 = 3;
 const YEAR: u16 = 2023;
 
 pub fn part_one(input: &str) -> Option<u32> {
-    let grid: CustomGrid<char> = input_to_grid::<char>(input).unwrap();
+    let grid: CustomGrid<char> = unwrap_parsed(input_to_grid::<char>(input));
 
     let mut iterator = grid.indexed_iter().peekable();
     let mut sum = 0;
@@ -55,7 +56,7 @@ pub fn part_one(input: &str) -> Option<u32> {
 
 
 pub fn part_two(input: &str) -> Option<u32> {
-    let grid: CustomGrid<char> = input_to_grid::<char>(input).unwrap();
+    let grid: CustomGrid<char> = unwrap_parsed(input_to_grid::<char>(input));
 
     let mut iterator = grid.indexed_iter().peekable();
     let mut gears: BTreeMap<(usize, usize), Vec<u32>> = BTreeMap::new();
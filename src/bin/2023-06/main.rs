@@ -1,4 +1,6 @@
 use advent_of_code::{read_input, should_submit, submit};
+use advent_of_code::ints::ints;
+use advent_of_code::math::count_between_roots;
 use std::time::Instant;
 
 const DAY: u8 = 6;
@@ -11,10 +13,10 @@ struct Race {
 
 fn parse_input_1(input: &str) -> Vec<Race> {
     let mut lines = input.lines();
-    let times: Vec<_> = lines.next().expect("times").split_ascii_whitespace().filter_map(|s| s.parse::<u64>().ok()).collect();
-    let distances: Vec<_> = lines.next().expect("distances").split_ascii_whitespace().filter_map(|s| s.parse::<u64>().ok()).collect();
+    let times = ints::<u64>(lines.next().expect("times"));
+    let distances = ints::<u64>(lines.next().expect("distances"));
 
-    times.into_iter().zip(distances)
+    times.zip(distances)
         .map(|(time, distance)| Race {
             time,
             distance,
@@ -22,19 +24,15 @@ fn parse_input_1(input: &str) -> Vec<Race> {
         .collect()
 }
 
+// Holding x beats the record when x * (time - x) > distance, i.e. x² - time * x + distance < 0
 fn solve_race(race: &Race) -> u64 {
-    let t = race.time as f64;
-    let d = race.distance as f64;
-    let x_min = (0.5f64 * (t - (t * t - 4f64 * d).sqrt())).floor() as u64 + 1;
-    let x_max = (0.5f64 * (t + (t * t - 4f64 * d).sqrt())).ceil() as u64 - 1;
-
-    x_max - x_min + 1
+    count_between_roots(1, -(race.time as i64), race.distance as i64)
 }
 
 pub fn part_one(input: &str) -> Option<u64> {
     let races = parse_input_1(input);
 
-    let product = races.iter().map(|race| solve_race(race)).product();
+    let product = races.iter().map(solve_race).product();
 
     Some(product)
 }
@@ -1,8 +1,11 @@
-use std::collections::HashMap;
 use std::time::Instant;
-use num::integer::lcm;
 
-use advent_of_code::{read_input, should_submit, submit};
+use advent_of_code::parse::unwrap_parsed;
+use advent_of_code::{read_input, sections, should_submit, submit};
+use advent_of_code::cycle::{Cycle, find_cycle};
+use advent_of_code::graph::{Graph, NodeId};
+use advent_of_code::math::align_cycles;
+use itertools::Itertools;
 
 const DAY: u8 = 8;
 const YEAR: u16 = 2023;
@@ -13,39 +16,41 @@ enum Direction {
     Left,
 }
 
-fn parse_input(input: &str) -> (Vec<Direction>, HashMap<(&str, Direction), &str>) {
-    let (directions, nodes) = input.split_once("\n\n").expect("split directions and nodes");
+fn parse_input(input: &str) -> (Vec<Direction>, Graph) {
+    let mut sections = sections(input);
+    let directions = sections.next().expect("directions");
+    let nodes = sections.next().expect("nodes");
     let directions: Vec<_> = directions.chars().map(|c| match c {
         'L' => Direction::Left,
         'R' => Direction::Right,
         _ => panic!("Unhandled direction char")
     }).collect();
 
-    let nodes = nodes.lines().flat_map(|line| {
-        let node = &line[..3];
-        let left_node = &line[7..10];
-        let right_node = &line[12..15];
+    (directions, unwrap_parsed(Graph::parse_branches(nodes)))
+}
 
-        [
-            ((node, Direction::Left), left_node),
-            ((node, Direction::Right), right_node)
-        ]
-    }).collect();
+// `AAA = (BBB, CCC)`: the left node is the first edge, the right one the second
+fn next_node(graph: &Graph, node: NodeId, direction: Direction) -> NodeId {
+    let (left_node, _) = graph.edges(node)[0];
+    let (right_node, _) = graph.edges(node)[1];
 
-    (directions, nodes)
+    match direction {
+        Direction::Left => left_node,
+        Direction::Right => right_node,
+    }
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
-    let (directions, nodes) = parse_input(input);
+    let (directions, graph) = parse_input(input);
 
     let mut n = 0;
-    let mut current_node = "AAA";
+    let mut current_node = graph.id("AAA")?;
 
     for direction in directions.iter().cycle() {
-        if current_node == "ZZZ" {
+        if graph.name(current_node) == "ZZZ" {
             break;
         }
-        current_node = nodes.get(&(current_node, *direction)).unwrap();
+        current_node = next_node(&graph, current_node, *direction);
         n += 1;
     }
 
@@ -53,35 +58,40 @@ pub fn part_one(input: &str) -> Option<u32> {
 }
 
 
+// Each ghost goes through (node, direction index) states that end up in a cycle, the nodes ending
+// with Z of the cycle coming back every `length` steps
 pub fn part_two(input: &str) -> Option<u64> {
-    let (directions, nodes) = parse_input(input);
+    let (directions, graph) = parse_input(input);
+    let step = |&(node, i): &(NodeId, usize)| (next_node(&graph, node, directions[i]), (i + 1) % directions.len());
+    let on_z = |&(node, _): &(NodeId, usize)| graph.name(node).ends_with('Z');
 
-    let mut n = 0;
-    let mut current_nodes: Vec<_> = nodes.keys()
-        .filter(|(node, direction)| node.ends_with('A') && direction.eq(&Direction::Left))
-        .map(|(node, _)| *node)
+    let ghosts: Vec<(Cycle, Vec<(NodeId, usize)>)> = graph.nodes()
+        .filter(|&node| graph.name(node).ends_with('A'))
+        .map(|node| find_cycle((node, 0), step))
         .collect();
 
-    let mut node_cycle: Vec<Option<u64>> = (0..current_nodes.len()).map(|_| None).collect();
-
-    for direction in directions.iter().cycle() {
-        for (i, node) in current_nodes.iter().enumerate() {
-            if node.ends_with('Z') {
-                node_cycle[i] = Some(n);
-            }
-        }
-
-        if node_cycle.iter().all(|cycle| cycle.is_some()) {
-            break;
-        }
-
-        for current_node in current_nodes.iter_mut() {
-            *current_node = nodes.get(&(current_node, *direction)).unwrap();
-        }
-        n += 1;
+    // Before every ghost is in its cycle, steps are only checked one by one
+    let prefix = ghosts.iter().map(|(cycle, _)| cycle.prefix).max()?;
+    if let Some(n) = (0..prefix).find(|&n| ghosts.iter().all(|(cycle, states)| on_z(&states[cycle.reduce(n)]))) {
+        return Some(n as u64);
     }
 
-    Some(node_cycle.into_iter().fold(1u64, |acc, cycle| lcm(acc, cycle.unwrap())))
+    // Then one of the Z nodes of each cycle has to line up with one of every other cycle
+    let z_cycles: Vec<Vec<(u64, u64)>> = ghosts
+        .iter()
+        .map(|(cycle, states)| {
+            (cycle.prefix..states.len())
+                .filter(|&n| on_z(&states[n]))
+                .map(|n| (n as u64, cycle.length as u64))
+                .collect()
+        })
+        .collect();
+
+    z_cycles
+        .iter()
+        .multi_cartesian_product()
+        .filter_map(|cycles| align_cycles(&cycles.into_iter().copied().collect::<Vec<_>>()))
+        .min()
 }
 
 fn main() {
@@ -155,4 +165,16 @@ mod tests {
         let example = read_example_2(DAY, YEAR);
         assert_eq!(part_two(&example), Some(6));
     }
+
+    #[test]
+    fn test_part_two_other_shapes() {
+        // Both ghosts end on a Z node once, before their cycle
+        let once = "L\n\n11A = (11Z, XXX)\n11Z = (XXX, XXX)\n22A = (22Z, XXX)\n22Z = (XXX, XXX)\nXXX = (XXX, XXX)";
+        assert_eq!(part_two(once), Some(1));
+
+        // The first ghost is on a Z node 2 steps out of 3, never when the second one is
+        let never = "L\n\n11A = (11B, XXX)\n11B = (11C, XXX)\n11C = (11Z, XXX)\n11Z = (12Z, XXX)\n12Z = (11C, XXX)\n\
+                     22A = (22B, XXX)\n22B = (22Z, XXX)\n22Z = (22C, XXX)\n22C = (22B, XXX)\nXXX = (XXX, XXX)";
+        assert_eq!(part_two(never), None);
+    }
 }
@@ -1,83 +1,37 @@
 use advent_of_code::{read_input, should_submit, submit};
+use advent_of_code::ints::ints;
+use advent_of_code::sequence::extrapolate;
 use std::time::Instant;
-use tailcall::tailcall;
 
 const DAY: u8 = 9;
 const YEAR: u16 = 2023;
 
-pub fn parse_input(input: &str) -> Vec<Vec<i32>> {
+pub fn parse_input(input: &str) -> Vec<Vec<i64>> {
     input
         .lines()
-        .map(|line|
-            line
-                .split_whitespace()
-                .map(|number| number.parse().unwrap())
-                .collect()
-        )
+        .map(|line| ints(line).collect())
         .collect()
 }
 
-
-fn serie_gaps(serie: &Vec<i32>) -> Vec<i32> {
-    serie
-        .windows(2)
-        .map(|slice| match slice {
-            &[a, b] => b - a,
-            _ => unreachable!(), // Handle other slice patterns if needed
-        })
-        .collect()
-}
-
-fn find_next(serie: &Vec<i32>) -> i32 {
-
-    #[tailcall]
-    fn find_next_inner(serie: &Vec<i32>) -> i32 {
-        let gaps = serie_gaps(serie);
-        if gaps.iter().all(|&n| n == 0) {
-            *serie.last().expect("No more element in serie")
-        } else {
-            serie.last().expect("No more element in serie") + find_next(&gaps)
-        }
-    }
-    find_next_inner(serie)
-}
-
-fn find_previous(serie: &Vec<i32>) -> i32 {
-    #[tailcall]
-    fn find_previous_inner(serie: &Vec<i32>) -> i32 {
-        let gaps = serie_gaps(serie);
-        if gaps.iter().all(|&n| n == 0) {
-            *serie.first().expect("No more element in serie")
-        } else {
-            serie.first().expect("No more element in serie") - find_previous(&gaps)
-        }
-    }
-    find_previous_inner(serie)
-}
-
-pub fn part_one(input: &str) -> Option<i32> {
+pub fn part_one(input: &str) -> Option<i128> {
     let series = parse_input(input);
 
     Some(
         series
             .into_iter()
-            .map(|serie| {
-                find_next(&serie)
-            })
+            .map(|serie| extrapolate(&serie, serie.len() as i64))
             .sum()
     )
 }
 
 
-pub fn part_two(input: &str) -> Option<i32> {
+pub fn part_two(input: &str) -> Option<i128> {
     let series = parse_input(input);
 
     Some(
         series
             .into_iter()
-            .map(|serie| {
-                find_previous(&serie)
-            })
+            .map(|serie| extrapolate(&serie, -1))
             .sum()
     )
 }
@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 use std::time::Instant;
 
+use advent_of_code::parse::unwrap_parsed;
 use advent_of_code::{
     CustomGrid, input_to_grid, read_input, should_submit, submit,
 };
@@ -152,7 +153,7 @@ fn count_inside_space(grid: &CustomGrid<char>) -> u32 {
 
 
 pub fn part_one(input: &str) -> Option<u32> {
-    let mut grid: CustomGrid<char> = input_to_grid(input).unwrap();
+    let mut grid: CustomGrid<char> = unwrap_parsed(input_to_grid(input));
 
     let (start_row, start_col) = start_point(&grid);
 
@@ -175,7 +176,7 @@ pub fn part_one(input: &str) -> Option<u32> {
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    let mut grid: CustomGrid<char> = input_to_grid(input).unwrap();
+    let mut grid: CustomGrid<char> = unwrap_parsed(input_to_grid(input));
 
     let (start_row, start_col) = start_point(&grid);
 
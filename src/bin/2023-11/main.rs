@@ -1,4 +1,5 @@
 use std::collections::HashMap;
+use advent_of_code::parse::unwrap_parsed;
 use advent_of_code::{CustomGrid, input_to_grid, read_input, should_submit, submit};
 use std::time::Instant;
 use itertools::Itertools;
@@ -7,7 +8,7 @@ const DAY: u8 = 11;
 const YEAR: u16 = 2023;
 
 fn parse_input(input: &str, gap: usize) -> Vec<(usize, usize)> {
-    let grid: CustomGrid<char> = input_to_grid(input).unwrap();
+    let grid: CustomGrid<char> = unwrap_parsed(input_to_grid(input));
 
     // For each row and cols, how many empty row/col is there ahead of it.
     // It is to know how much it should shift any galaxy at this row/col
@@ -1,6 +1,6 @@
 use advent_of_code::{read_input, should_submit, submit};
+use advent_of_code::memo::Memo;
 use std::time::Instant;
-use memoize::memoize;
 use rayon::prelude::*;
 
 const DAY: u8 = 12;
@@ -12,7 +12,7 @@ struct Row<'a> {
     consecutives: Vec<u8>,
 }
 
-fn parse_input(input: &str) -> Vec<Row> {
+fn parse_input(input: &str) -> Vec<Row<'_>> {
     input
         .lines()
         .map(|line| {
@@ -26,190 +26,116 @@ fn parse_input(input: &str) -> Vec<Row> {
         .collect()
 }
 
-#[memoize]
-fn solve_row(row: Row<'static>) -> u64 {
-    // let row_clone = row.clone();
-    // println!("solving {:?}", &row);
-    if row.consecutives.is_empty() {
-        return match row.pattern.contains('#') {
-            true => {
-                // println!("{row_clone:?}");
-                // println!("return 0 (consecutive empty, # remaining)");
-                0
-            }
-            false => {
-                // println!("{row_clone:?}");
-                // println!("return 1 (consecutive empty, no #)");
-                1
-            }
-        };
-    }
+// (pattern, consecutives) still to be matched
+type Key<'a> = (&'a str, &'a [u8]);
+
+// Arrangements of the pattern, placing the last consecutive first
+fn arrangements<'a>(recurse: &mut dyn FnMut(Key<'a>) -> u64, (pattern, consecutives): Key<'a>) -> u64 {
+    let Some((&consecutive, remaining)) = consecutives.split_last() else {
+        // No # can remain
+        return u64::from(!pattern.contains('#'));
+    };
 
-    if row.pattern.is_empty() {
-        // println!("{row_clone:?}");
-        // println!("return 0 (pattern empty)");
+    if pattern.is_empty() {
         return 0;
     }
 
-    let mut row = row.clone();
-    row.pattern = row.pattern.trim_end_matches('.'); // Remove trailing dots
-    let consecutive = row.consecutives.pop().unwrap() as usize;
+    let pattern = pattern.trim_end_matches('.'); // Remove trailing dots
+    let consecutive = consecutive as usize;
 
-    let last_dot_pos = row.pattern.rfind('.').map(|x| x);
+    let last_dot_pos = pattern.rfind('.');
 
     let left_space = match last_dot_pos {
-        None => row.pattern.len(),
-        Some(last_dot_pos) => row.pattern.len() - last_dot_pos - 1
+        None => pattern.len(),
+        Some(last_dot_pos) => pattern.len() - last_dot_pos - 1
     };
 
     if left_space < consecutive {
         return match last_dot_pos {
-            None => {
-                // println!("{row_clone:?}");
-                // println!("return 0 (no space left for a {consecutive} consecutive)");
-                0
-            }
-            Some(last_dot_pos) => {
-                if row.pattern[last_dot_pos..].contains('#') {
-                    // println!("{row_clone:?}");
-                    // println!("return 0 (cannot include # in a consecutive)");
-                    0
-                } else {
-                    let mut consecutive_with_popped = row.consecutives.clone();
-                    consecutive_with_popped.push(consecutive as u8);
-                    let sub_solve = solve_row(Row {
-                        consecutives: consecutive_with_popped,
-                        pattern: &row.pattern[..last_dot_pos],
-                    });
-
-                    // println!("{row_clone:?}");
-                    // println!("return {sub_solve} (no solution, went to subsolve for {:?})", &row.pattern[..last_dot_pos]);
-
-                    sub_solve
-                }
-            }
+            // No space left for the consecutive
+            None => 0,
+            // Cannot include # in a consecutive
+            Some(last_dot_pos) if pattern[last_dot_pos..].contains('#') => 0,
+            Some(last_dot_pos) => recurse((&pattern[..last_dot_pos], consecutives)),
         };
     }
 
-    let current = &row.pattern[row.pattern.len() - left_space..];
-    let rest = &row.pattern[..row.pattern.len() - left_space];
+    let current = &pattern[pattern.len() - left_space..];
+    let rest = &pattern[..pattern.len() - left_space];
 
     if left_space == consecutive {
-        let sub_case_replace_by_hashtag = Row {
-            consecutives: row.consecutives.clone(),
-            pattern: rest,
-        };
-
-        return if current.chars().all(|c| c.eq(&'?')) {
-            let mut consecutive_with_popped = row.consecutives.clone();
-            consecutive_with_popped.push(consecutive as u8);
-            let sub_case_replace_by_dot = Row {
-                consecutives: consecutive_with_popped,
-                pattern: rest,
-            };
+        let replace_by_hashtag = recurse((rest, remaining));
 
-            let sub_case_replace_by_dot_solve = solve_row(sub_case_replace_by_dot);
-            let sub_case_replace_by_hashtag_solve = solve_row(sub_case_replace_by_hashtag);
-
-            // println!("{row_clone:?}");
-            // println!("return {sub_case_replace_by_dot_solve} + {sub_case_replace_by_hashtag_solve} (dot + hashtag)");
-            sub_case_replace_by_dot_solve + sub_case_replace_by_hashtag_solve
+        return if current.bytes().all(|c| c == b'?') {
+            recurse((rest, consecutives)) + replace_by_hashtag
         } else {
-            let sub_case_replace_by_hashtag_solve = solve_row(sub_case_replace_by_hashtag);
-
-            // println!("{row_clone:?}");
-            // println!("return sub_case_replace_by_hashtag_solve (hashtag)");
-
-            sub_case_replace_by_hashtag_solve
+            replace_by_hashtag
         };
     }
 
-    let possibilities = left_space - consecutive as usize + 1;
-    let start_looking = row.pattern.len() - left_space;
+    let possibilities = left_space - consecutive + 1;
+    let start_looking = pattern.len() - left_space;
     let end_looking = start_looking + possibilities;
 
     let mut total = 0;
 
     // Consider replacing everything by dots
     if let Some(last_dot_pos) = last_dot_pos {
-        if !row.pattern[last_dot_pos..].contains('#') {
-            let mut consecutive_with_popped = row.consecutives.clone();
-            consecutive_with_popped.push(consecutive as u8);
-            let sub_solve = solve_row(Row {
-                consecutives: consecutive_with_popped,
-                pattern: &row.pattern[..last_dot_pos],
-            });
-
-            total += sub_solve;
+        if !pattern[last_dot_pos..].contains('#') {
+            total += recurse((&pattern[..last_dot_pos], consecutives));
         }
     }
 
-
     for i in start_looking..end_looking {
         let before = match i {
-            0 => '.',
-            i @ _ => row.pattern.chars().nth(i - 1).unwrap()
+            0 => b'.',
+            i => pattern.as_bytes()[i - 1]
         };
-        let after = &row.pattern[i + consecutive..];
-        // println!("{i} before: {before} after: {after}");
-        if (before == '?' || before == '.') &&
-            after.chars().all(|c| c.eq(&'?')) {
-            let sub_row = match i {
-                0 => Row {
-                    consecutives: row.consecutives.clone(),
-                    pattern: "",
-                },
-                i @ _ => Row {
-                    consecutives: row.consecutives.clone(),
-                    pattern: &row.pattern[..i - 1],
-                }
+        let after = &pattern[i + consecutive..];
+        if (before == b'?' || before == b'.') && after.bytes().all(|c| c == b'?') {
+            let sub_pattern = match i {
+                0 => "",
+                i => &pattern[..i - 1],
             };
 
-            total += solve_row(sub_row)
+            total += recurse((sub_pattern, remaining));
         }
     }
 
-    // println!("{row_clone:?}");
-    // println!("return sum of {:?}: {}", &total, total.iter().sum::<u64>());
     total
 }
 
-fn solve(rows: Vec<Row<'static>>) -> u64 {
-    rows
-        .par_iter()
-        .map(|row| {
-            let solve = solve_row(row.clone());
-            // println!("{:?} {:?} {}", row.pattern, row.consecutives, solve);
-            solve
-        })
-        .sum()
+fn solve_row(row: Row) -> u64 {
+    let mut memo = Memo::new();
+    memo.solve((row.pattern, &row.consecutives[..]), &arrangements)
+}
+
+fn solve(rows: Vec<Row>) -> u64 {
+    rows.into_par_iter().map(solve_row).sum()
 }
 
 pub fn part_one(input: &str) -> Option<u64> {
-    let input = input.to_string().leak();
     let input = parse_input(input);
 
     Some(solve(input))
 }
 
-fn complicate_things(input: &mut Vec<Row>) {
-    for row in input.iter_mut() {
-        let mut duplicated = format!("{}?", row.pattern).repeat(5);
-        duplicated.pop(); // remove trailing ?
-        row.pattern = duplicated.leak();
-        row.consecutives = row.consecutives.repeat(5);
+fn unfold(row: &Row) -> (String, Vec<u8>) {
+    let mut pattern = format!("{}?", row.pattern).repeat(5);
+    pattern.pop(); // remove trailing ?
 
-        // println!("{:?}", row);
-    }
+    (pattern, row.consecutives.repeat(5))
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
-    let input = input.to_string().leak();
-    let mut input = parse_input(input);
-
-    complicate_things(&mut input);
-
-    Some(solve(input))
+    let (patterns, consecutives): (Vec<_>, Vec<_>) = parse_input(input).iter().map(unfold).unzip();
+    let rows = patterns
+        .iter()
+        .zip(consecutives)
+        .map(|(pattern, consecutives)| Row { pattern, consecutives })
+        .collect();
+
+    Some(solve(rows))
 }
 
 fn main() {
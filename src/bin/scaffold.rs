@@ -4,25 +4,39 @@ use std::process;
 use clap::Parser;
 use advent_of_code::{fetch_puzzle_and_input, get_example_path, get_folder_path, get_input_path, get_module_path, get_puzzle_path};
 
-const TEMPLATE: &str = r###"use advent_of_code::{read_input, should_submit, submit};
+const TEMPLATE: &str = r###"use advent_of_code::parse::ParseError;
+use advent_of_code::{input_to_list, read_input, should_submit, submit};
+use std::process;
 use std::time::Instant;
 
 const DAY: u8 = {DAY};
 const YEAR: u16 = {YEAR};
 
-pub fn part_one(input: &str) -> Option<u32> {
+fn parse(input: &str) -> Result<Vec<String>, ParseError> {
+    // TODO: implem
+    input_to_list(input)
+}
+
+pub fn part_one(lines: &[String]) -> Option<u32> {
     // TODO: implem
     None
 }
 
 
-pub fn part_two(input: &str) -> Option<u32> {
+pub fn part_two(lines: &[String]) -> Option<u32> {
     // TODO: implem
     None
 }
 
 fn main() {
-    let input = read_input(DAY, YEAR);
+    let input = match parse(&read_input(DAY, YEAR)) {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    };
+
     let now = Instant::now();
     let part_one_response = part_one(&input);
     let ellapsed = now.elapsed().as_micros();
@@ -77,19 +91,20 @@ fn main() {
 
 #[cfg(test)]
 mod tests {
+    use advent_of_code::parse::unwrap_parsed;
     use advent_of_code::read_example;
-    use super::{DAY, YEAR, part_one, part_two};
+    use super::{DAY, YEAR, parse, part_one, part_two};
 
     #[test]
     fn test_part_one() {
-        let example = read_example(DAY, YEAR);
+        let example = unwrap_parsed(parse(&read_example(DAY, YEAR)));
         // TODO: set example expected response
         assert_eq!(part_one(&example), None);
     }
 
     #[test]
     fn test_part_two() {
-        let example = read_example(DAY, YEAR);
+        let example = unwrap_parsed(parse(&read_example(DAY, YEAR)));
         // TODO: set example expected response
         assert_eq!(part_two(&example), None);
     }
@@ -0,0 +1,426 @@
+use std::fmt::{Debug, Display, Formatter};
+use std::ops::{BitAnd, BitOr, BitXor, Not, Sub};
+use std::str::FromStr;
+
+use crate::parse::ParseError;
+
+/// Set of positions below a `width` of at most 64, held in a single word.
+///
+/// The width is what [`Not`] complements within, and the number of characters of the binary text:
+/// `"10110"` is read like `u64::from_str_radix(_, 2)`, its first character being position 4.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Bits {
+    bits: u64,
+    width: u32,
+}
+
+/// Width of the sets read from letters, `a` to `z` being positions 0 to 25 and `A` to `Z` 26 to 51.
+pub const LETTERS: u32 = 52;
+
+impl Bits {
+    pub fn new(width: u32) -> Self {
+        assert!(width <= 64, "a width of {width} doesn't fit in 64 bits");
+        Self { bits: 0, width }
+    }
+
+    pub fn from_word(bits: u64, width: u32) -> Self {
+        let set = Self::new(width);
+        assert!(bits & !set.mask() == 0, "{bits:#b} has bits past a width of {width}");
+        Self { bits, ..set }
+    }
+
+    /// Set of the letters of `text`, see [`LETTERS`].
+    pub fn from_letters(text: &str) -> Result<Self, ParseError> {
+        let mut set = Self::new(LETTERS);
+        for (i, c) in text.chars().enumerate() {
+            let position = match c {
+                'a'..='z' => c as u32 - 'a' as u32,
+                'A'..='Z' => c as u32 - 'A' as u32 + 26,
+                _ => return Err(ParseError::new(1, i + 1, text, "a letter")),
+            };
+            set.insert(position);
+        }
+        Ok(set)
+    }
+
+    fn mask(&self) -> u64 {
+        u64::MAX.checked_shr(64 - self.width).unwrap_or(0)
+    }
+
+    pub fn word(&self) -> u64 {
+        self.bits
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn contains(&self, position: u32) -> bool {
+        position < self.width && self.bits >> position & 1 == 1
+    }
+
+    /// Whether `position` wasn't there already.
+    pub fn insert(&mut self, position: u32) -> bool {
+        assert!(position < self.width, "position {position} out of a width of {}", self.width);
+        let added = !self.contains(position);
+        self.bits |= 1 << position;
+        added
+    }
+
+    /// Whether `position` was there.
+    pub fn remove(&mut self, position: u32) -> bool {
+        let removed = self.contains(position);
+        self.bits &= !(1u64.checked_shl(position).unwrap_or(0));
+        removed
+    }
+
+    pub fn len(&self) -> u32 {
+        self.bits.count_ones()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bits == 0
+    }
+
+    /// Positions in the set, smallest first.
+    pub fn iter(&self) -> impl Iterator<Item=u32> {
+        let mut bits = self.bits;
+        std::iter::from_fn(move || {
+            (bits != 0).then(|| {
+                let position = bits.trailing_zeros();
+                bits &= bits - 1;
+                position
+            })
+        })
+    }
+
+    pub fn is_subset(&self, other: &Self) -> bool {
+        self.bits & !other.bits == 0
+    }
+
+    pub fn is_superset(&self, other: &Self) -> bool {
+        other.is_subset(self)
+    }
+
+    /// Letters of the set in alphabetical order, see [`LETTERS`].
+    pub fn letters(&self) -> String {
+        self.iter()
+            .map(|position| match position {
+                0..=25 => (b'a' + position as u8) as char,
+                _ => (b'A' + (position - 26) as u8) as char,
+            })
+            .collect()
+    }
+
+    fn with(self, other: Self, bits: u64) -> Self {
+        Self {
+            bits,
+            width: self.width.max(other.width),
+        }
+    }
+}
+
+impl BitOr for Bits {
+    type Output = Self;
+
+    fn bitor(self, other: Self) -> Self::Output {
+        self.with(other, self.bits | other.bits)
+    }
+}
+
+impl BitAnd for Bits {
+    type Output = Self;
+
+    fn bitand(self, other: Self) -> Self::Output {
+        self.with(other, self.bits & other.bits)
+    }
+}
+
+impl BitXor for Bits {
+    type Output = Self;
+
+    fn bitxor(self, other: Self) -> Self::Output {
+        self.with(other, self.bits ^ other.bits)
+    }
+}
+
+impl Sub for Bits {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self::Output {
+        self.with(other, self.bits & !other.bits)
+    }
+}
+
+/// Positions below the width that aren't in the set.
+impl Not for Bits {
+    type Output = Self;
+
+    fn not(self) -> Self::Output {
+        Self {
+            bits: !self.bits & self.mask(),
+            ..self
+        }
+    }
+}
+
+impl FromStr for Bits {
+    type Err = ParseError;
+
+    /// Binary text, a `'1'` being a position in the set.
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let text = text.trim();
+        if text.len() > 64 {
+            return Err(ParseError::new(1, 65, text, "at most 64 bits"));
+        }
+
+        let mut set = Self::new(text.len() as u32);
+        for (i, byte) in text.bytes().enumerate() {
+            match byte {
+                b'0' => {}
+                b'1' => {
+                    set.insert((text.len() - 1 - i) as u32);
+                }
+                _ => return Err(ParseError::new(1, i + 1, text, "`0` or `1`")),
+            }
+        }
+        Ok(set)
+    }
+}
+
+impl Display for Bits {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        (0..self.width).rev().try_for_each(|position| write!(f, "{}", self.contains(position) as u8))
+    }
+}
+
+impl Debug for Bits {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+/// How many of `sets` hold each position, up to the widest of them.
+pub fn bit_counts(sets: &[Bits]) -> Vec<usize> {
+    let width = sets.iter().map(Bits::width).max().unwrap_or(0);
+    let mut counts = vec![0; width as usize];
+    for set in sets {
+        set.iter().for_each(|position| counts[position as usize] += 1);
+    }
+    counts
+}
+
+/// Set of positions of any size, growing as they are inserted.
+#[derive(Clone, Default, PartialEq, Eq, Hash)]
+pub struct BitSet {
+    // Never ends with an empty word, so that equal sets are equal words
+    words: Vec<u64>,
+}
+
+impl BitSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn trim(&mut self) {
+        while self.words.last() == Some(&0) {
+            self.words.pop();
+        }
+    }
+
+    pub fn contains(&self, position: usize) -> bool {
+        self.words.get(position / 64).is_some_and(|word| word >> (position % 64) & 1 == 1)
+    }
+
+    /// Whether `position` wasn't there already.
+    pub fn insert(&mut self, position: usize) -> bool {
+        let index = position / 64;
+        if index >= self.words.len() {
+            self.words.resize(index + 1, 0);
+        }
+
+        let added = !self.contains(position);
+        self.words[index] |= 1 << (position % 64);
+        added
+    }
+
+    /// Whether `position` was there.
+    pub fn remove(&mut self, position: usize) -> bool {
+        let removed = self.contains(position);
+        if removed {
+            self.words[position / 64] &= !(1 << (position % 64));
+            self.trim();
+        }
+        removed
+    }
+
+    pub fn len(&self) -> usize {
+        self.words.iter().map(|word| word.count_ones() as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.words.clear();
+    }
+
+    /// Positions in the set, smallest first.
+    pub fn iter(&self) -> impl Iterator<Item=usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(index, &word)| {
+            Bits::from_word(word, 64).iter().map(move |position| index * 64 + position as usize)
+        })
+    }
+
+    pub fn union_with(&mut self, other: &Self) {
+        if other.words.len() > self.words.len() {
+            self.words.resize(other.words.len(), 0);
+        }
+        self.words.iter_mut().zip(&other.words).for_each(|(word, other)| *word |= other);
+    }
+
+    pub fn intersect_with(&mut self, other: &Self) {
+        self.words.truncate(other.words.len());
+        self.words.iter_mut().zip(&other.words).for_each(|(word, other)| *word &= other);
+        self.trim();
+    }
+
+    pub fn difference_with(&mut self, other: &Self) {
+        self.words.iter_mut().zip(&other.words).for_each(|(word, other)| *word &= !other);
+        self.trim();
+    }
+
+    pub fn is_subset(&self, other: &Self) -> bool {
+        self.words.len() <= other.words.len()
+            && self.words.iter().zip(&other.words).all(|(word, other)| word & !other == 0)
+    }
+
+    pub fn is_superset(&self, other: &Self) -> bool {
+        other.is_subset(self)
+    }
+}
+
+impl BitOr for &BitSet {
+    type Output = BitSet;
+
+    fn bitor(self, other: Self) -> Self::Output {
+        let mut set = self.clone();
+        set.union_with(other);
+        set
+    }
+}
+
+impl BitAnd for &BitSet {
+    type Output = BitSet;
+
+    fn bitand(self, other: Self) -> Self::Output {
+        let mut set = self.clone();
+        set.intersect_with(other);
+        set
+    }
+}
+
+impl Sub for &BitSet {
+    type Output = BitSet;
+
+    fn sub(self, other: Self) -> Self::Output {
+        let mut set = self.clone();
+        set.difference_with(other);
+        set
+    }
+}
+
+impl Extend<usize> for BitSet {
+    fn extend<I: IntoIterator<Item=usize>>(&mut self, positions: I) {
+        positions.into_iter().for_each(|position| {
+            self.insert(position);
+        });
+    }
+}
+
+impl FromIterator<usize> for BitSet {
+    fn from_iter<I: IntoIterator<Item=usize>>(positions: I) -> Self {
+        let mut set = Self::new();
+        set.extend(positions);
+        set
+    }
+}
+
+impl From<Bits> for BitSet {
+    fn from(bits: Bits) -> Self {
+        bits.iter().map(|position| position as usize).collect()
+    }
+}
+
+impl Debug for BitSet {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use crate::testing::Random;
+
+    use super::{bit_counts, BitSet, Bits};
+
+    #[test]
+    fn binary_text() {
+        let set: Bits = "10110".parse().unwrap();
+        assert_eq!((set.word(), set.width(), set.len()), (0b10110, 5, 3));
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![1, 2, 4]);
+        assert_eq!((!set).to_string(), "01001");
+        assert_eq!(format!("{set:?}"), "{1, 2, 4}");
+
+        let error = "10210".parse::<Bits>().unwrap_err();
+        assert_eq!((error.column, error.expected.as_str()), (3, "`0` or `1`"));
+
+        let sets: Vec<Bits> = ["00100", "11110", "10110"].iter().map(|text| text.parse().unwrap()).collect();
+        assert_eq!(bit_counts(&sets), vec![0, 2, 3, 1, 2]);
+        assert_eq!((!Bits::new(64)).len(), 64);
+    }
+
+    #[test]
+    fn letter_sets() {
+        let one = Bits::from_letters("ab").unwrap();
+        let seven = Bits::from_letters("dab").unwrap();
+        let zero = Bits::from_letters("cagedb").unwrap();
+
+        assert_eq!((seven - one).letters(), "d");
+        assert_eq!((one | seven, one & zero, seven ^ zero), (seven, one, Bits::from_letters("ceg").unwrap()));
+        assert!(one.is_subset(&seven) && zero.is_superset(&seven) && !seven.is_subset(&one));
+        assert_eq!(Bits::from_letters("bad").unwrap(), seven);
+        assert_eq!(Bits::from_letters("zA").unwrap().letters(), "zA");
+        assert_eq!(Bits::from_letters("a1").unwrap_err().column, 2);
+    }
+
+    #[test]
+    fn growable_against_btree_set() {
+        let mut random = Random::new(7);
+
+        for _ in 0..50 {
+            let a: Vec<usize> = (0..random.below(40)).map(|_| random.below(300) as usize).collect();
+            let b: Vec<usize> = (0..random.below(40)).map(|_| random.below(200) as usize).collect();
+            let (set_a, set_b): (BitSet, BitSet) = (a.iter().copied().collect(), b.iter().copied().collect());
+            let (tree_a, tree_b): (BTreeSet<usize>, BTreeSet<usize>) = (a.into_iter().collect(), b.into_iter().collect());
+
+            let items = |set: &BitSet| set.iter().collect::<Vec<_>>();
+            assert_eq!(items(&set_a), tree_a.iter().copied().collect::<Vec<_>>());
+            assert_eq!(set_a.len(), tree_a.len());
+            assert_eq!(items(&(&set_a | &set_b)), tree_a.union(&tree_b).copied().collect::<Vec<_>>());
+            assert_eq!(items(&(&set_a & &set_b)), tree_a.intersection(&tree_b).copied().collect::<Vec<_>>());
+            assert_eq!(items(&(&set_a - &set_b)), tree_a.difference(&tree_b).copied().collect::<Vec<_>>());
+            assert_eq!(set_a.is_subset(&set_b), tree_a.is_subset(&tree_b));
+            assert!((&set_a & &set_b).is_subset(&set_a));
+        }
+
+        // Removing the highest positions gives a set equal to one that never had them
+        let mut set: BitSet = [3, 200].into_iter().collect();
+        assert!(set.remove(200) && !set.remove(200));
+        assert_eq!(set, [3].into_iter().collect());
+        assert_eq!(BitSet::from(Bits::from_letters("ace").unwrap()), [0, 2, 4].into_iter().collect());
+    }
+}
@@ -0,0 +1,213 @@
+use std::borrow::Cow;
+use std::ops::Index;
+
+use crate::parse::ParseError;
+use crate::CustomGrid;
+use grid::Grid;
+
+/// Lines of `input` without their `\n` or `\r\n`, with no allocation.
+pub fn byte_lines(input: &[u8]) -> ByteLines<'_> {
+    ByteLines { rest: input }
+}
+
+pub struct ByteLines<'a> {
+    rest: &'a [u8],
+}
+
+impl<'a> Iterator for ByteLines<'a> {
+    type Item = &'a [u8];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.rest.is_empty() {
+            return None;
+        }
+
+        let (line, rest) = match self.rest.iter().position(|&byte| byte == b'\n') {
+            Some(end) => (&self.rest[..end], &self.rest[end + 1..]),
+            None => (self.rest, &[][..]),
+        };
+        self.rest = rest;
+
+        Some(line.strip_suffix(b"\r").unwrap_or(line))
+    }
+}
+
+/// Value of an ASCII digit.
+pub fn digit(byte: u8) -> Option<u8> {
+    byte.is_ascii_digit().then(|| byte - b'0')
+}
+
+/// Unsigned number made of all of `bytes`, `None` if one isn't a digit or on overflow.
+pub fn parse_u64(bytes: &[u8]) -> Option<u64> {
+    if bytes.is_empty() {
+        return None;
+    }
+
+    bytes.iter().try_fold(0u64, |number, &byte| {
+        number.checked_mul(10)?.checked_add(digit(byte)? as u64)
+    })
+}
+
+/// Same as [`parse_u64`], with an optional `-` or `+` sign.
+pub fn parse_i64(bytes: &[u8]) -> Option<i64> {
+    match bytes.split_first() {
+        Some((b'-', digits)) => 0i64.checked_sub_unsigned(parse_u64(digits)?),
+        Some((b'+', digits)) => parse_u64(digits)?.try_into().ok(),
+        _ => parse_u64(bytes)?.try_into().ok(),
+    }
+}
+
+/// Position of the first occurrence of `needle` in `haystack`.
+pub fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    if needle.is_empty() {
+        return Some(0);
+    }
+    haystack.windows(needle.len()).position(|window| window == needle)
+}
+
+/// Words of `bytes` separated by any amount of ASCII whitespace.
+pub fn fields(bytes: &[u8]) -> impl Iterator<Item=&[u8]> {
+    bytes.split(u8::is_ascii_whitespace).filter(|field| !field.is_empty())
+}
+
+/// Rectangular grid read in place from the bytes of the input, cells being indexed in O(1) as
+/// `(row, col)` without copying anything.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ByteGrid<'a> {
+    bytes: &'a [u8],
+    rows: usize,
+    cols: usize,
+    // Distance between the starts of two lines, line ending included
+    stride: usize,
+}
+
+impl<'a> ByteGrid<'a> {
+    /// Fails on a line not as long as the first one, or not ending like it.
+    pub fn new(input: &'a [u8]) -> Result<Self, ParseError> {
+        // Only line endings, as trailing spaces can be cells of the last row
+        let end = input.len() - input.iter().rev().take_while(|&&byte| byte == b'\n' || byte == b'\r').count();
+        let bytes = &input[..end];
+
+        let cols = bytes.iter().position(|&byte| byte == b'\r' || byte == b'\n').unwrap_or(bytes.len());
+        let stride = match bytes.get(cols) {
+            Some(b'\r') => cols + 2,
+            _ => cols + 1,
+        };
+        let ending = bytes.get(cols..stride.min(bytes.len())).unwrap_or(&[]);
+
+        let mut rows = 0;
+        for (i, line) in byte_lines(bytes).enumerate() {
+            let start = i * stride;
+            let text = || String::from_utf8_lossy(line);
+            if line.len() != cols {
+                let expected = format!("{cols} cells like the first line, found {}", line.len());
+                return Err(ParseError::new(i + 1, cols.min(line.len()) + 1, &text(), &expected));
+            }
+            if start + cols < bytes.len() && &bytes[start + cols..start + stride] != ending {
+                return Err(ParseError::new(i + 1, cols + 1, &text(), "the line ending of the first line"));
+            }
+            rows += 1;
+        }
+
+        Ok(Self { bytes, rows, cols, stride })
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> Option<u8> {
+        (row < self.rows && col < self.cols).then(|| self.bytes[row * self.stride + col])
+    }
+
+    pub fn row(&self, row: usize) -> &'a [u8] {
+        assert!(row < self.rows, "row {row} out of a grid of {} rows", self.rows);
+        &self.bytes[row * self.stride..row * self.stride + self.cols]
+    }
+
+    pub fn iter_rows(&self) -> impl Iterator<Item=&'a [u8]> + '_ {
+        (0..self.rows).map(|row| self.row(row))
+    }
+
+    /// Cells as `((row, col), byte)`, row by row.
+    pub fn indexed_iter(&self) -> impl Iterator<Item=((usize, usize), u8)> + '_ {
+        self.iter_rows()
+            .enumerate()
+            .flat_map(|(row, bytes)| bytes.iter().enumerate().map(move |(col, &byte)| ((row, col), byte)))
+    }
+
+    pub fn position(&self, byte: u8) -> Option<(usize, usize)> {
+        self.indexed_iter().find(|&(_, b)| b == byte).map(|(position, _)| position)
+    }
+
+    /// Copy of the cells as a grid that can be modified.
+    pub fn to_grid(&self) -> CustomGrid<u8> {
+        let mut data = Vec::with_capacity(self.rows * self.cols);
+        self.iter_rows().for_each(|row| data.extend_from_slice(row));
+        CustomGrid(Grid::from_vec(data, self.cols))
+    }
+
+    pub fn to_string_lossy(&self) -> Cow<'a, str> {
+        String::from_utf8_lossy(self.bytes)
+    }
+}
+
+impl Index<(usize, usize)> for ByteGrid<'_> {
+    type Output = u8;
+
+    fn index(&self, (row, col): (usize, usize)) -> &Self::Output {
+        assert!(row < self.rows && col < self.cols, "({row}, {col}) out of a {}x{} grid", self.rows, self.cols);
+        &self.bytes[row * self.stride + col]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{byte_lines, digit, fields, find, parse_i64, parse_u64, ByteGrid};
+
+    #[test]
+    fn lines_and_helpers() {
+        let lines: Vec<_> = byte_lines(b"ab\r\n\ncd\n").collect();
+        assert_eq!(lines, vec![&b"ab"[..], b"", b"cd"]);
+        assert_eq!(byte_lines(b"").count(), 0);
+
+        assert_eq!((digit(b'7'), digit(b'a')), (Some(7), None));
+        assert_eq!(parse_u64(b"18446744073709551615"), Some(u64::MAX));
+        assert_eq!(parse_u64(b"18446744073709551616"), None);
+        assert_eq!((parse_u64(b""), parse_u64(b"1a")), (None, None));
+        assert_eq!(parse_i64(b"-9223372036854775808"), Some(i64::MIN));
+        assert_eq!((parse_i64(b"+12"), parse_i64(b"-")), (Some(12), None));
+
+        assert_eq!(find(b"eightwothree", b"two"), Some(4));
+        assert_eq!(find(b"one", b"three"), None);
+        assert_eq!(fields(b"  Time:   7 15\t30 ").collect::<Vec<_>>(), vec![&b"Time:"[..], b"7", b"15", b"30"]);
+    }
+
+    #[test]
+    fn grid_in_place() {
+        for input in ["#.S\n..#\n", "#.S\r\n..#\r\n", "#.S\n..#"] {
+            let grid = ByteGrid::new(input.as_bytes()).unwrap();
+            assert_eq!((grid.rows(), grid.cols()), (2, 3));
+            assert_eq!((grid[(1, 2)], grid.get(1, 3), grid.get(2, 0)), (b'#', None, None));
+            assert_eq!(grid.row(1), b"..#");
+            assert_eq!(grid.position(b'S'), Some((0, 2)));
+            assert_eq!(grid.to_grid().iter().filter(|&&b| b == b'#').count(), 2);
+        }
+
+        let error = ByteGrid::new(b"#.S\n..\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 3));
+
+        let error = ByteGrid::new(b"#.S\r\n..#\n...").unwrap_err();
+        assert_eq!((error.line, error.expected.as_str()), (2, "the line ending of the first line"));
+
+        assert_eq!(ByteGrid::new(b"").unwrap().rows(), 0);
+
+        // Last row padded with spaces, as a map drawn with them
+        let grid = ByteGrid::new(b" #. \n  # \n#   \n").unwrap();
+        assert_eq!((grid.rows(), grid.cols(), grid.row(2)), (3, 4, &b"#   "[..]));
+    }
+}
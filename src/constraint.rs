@@ -0,0 +1,320 @@
+use crate::bitset::BitSet;
+
+/// A variable of a [`Problem`], standing for its index in the solutions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Var(usize);
+
+impl Var {
+    pub fn index(&self) -> usize {
+        self.0
+    }
+}
+
+type Relation<'a, T> = Box<dyn Fn(&T, &T) -> bool + 'a>;
+type Predicate<'a, T> = Box<dyn Fn(&[&T]) -> bool + 'a>;
+
+enum Constraint<'a, T> {
+    AllDifferent(Vec<Var>),
+    Relation(Var, Var, Relation<'a, T>),
+    Custom(Vec<Var>, Predicate<'a, T>),
+}
+
+/// Finite domain constraint problem: variables taking one of their values each, so that every
+/// constraint holds.
+///
+/// Domains are narrowed by propagating the constraints until none of them removes a value, which
+/// leaves every [`Problem::relation`] arc consistent, then the variable with the fewest values left is
+/// tried with each of them in turn.
+pub struct Problem<'a, T> {
+    values: Vec<Vec<T>>,
+    constraints: Vec<Constraint<'a, T>>,
+}
+
+impl<'a, T: PartialEq> Problem<'a, T> {
+    pub fn new() -> Self {
+        Self {
+            values: vec![],
+            constraints: vec![],
+        }
+    }
+
+    /// Variable taking one of `values`, repeated ones being only kept once.
+    pub fn variable(&mut self, values: impl IntoIterator<Item=T>) -> Var {
+        let mut domain = vec![];
+        for value in values {
+            if !domain.contains(&value) {
+                domain.push(value);
+            }
+        }
+        self.values.push(domain);
+        Var(self.values.len() - 1)
+    }
+
+    /// No two of `variables` take equal values.
+    pub fn all_different(&mut self, variables: &[Var]) {
+        self.constraints.push(Constraint::AllDifferent(variables.to_vec()));
+    }
+
+    /// `relation` holds between the values of `a` and `b`.
+    pub fn relation(&mut self, a: Var, b: Var, relation: impl Fn(&T, &T) -> bool + 'a) {
+        self.constraints.push(Constraint::Relation(a, b, Box::new(relation)));
+    }
+
+    /// `predicate` holds for the values of `variables`, in the same order. It is only checked
+    /// once all of them but one have a single value left.
+    pub fn constraint(&mut self, variables: &[Var], predicate: impl Fn(&[&T]) -> bool + 'a) {
+        self.constraints.push(Constraint::Custom(variables.to_vec(), Box::new(predicate)));
+    }
+
+    fn initial_domains(&self) -> Vec<BitSet> {
+        self.values.iter().map(|values| (0..values.len()).collect()).collect()
+    }
+
+    // Value of `variable` when it has a single one left
+    fn assigned(&self, domains: &[BitSet], variable: Var) -> Option<&T> {
+        let domain = &domains[variable.0];
+        (domain.len() == 1).then(|| &self.values[variable.0][domain.iter().next().unwrap()])
+    }
+
+    // Removes the values of `variable` whose index `keep` is false for, telling whether any was
+    fn retain(&self, domains: &mut [BitSet], variable: Var, mut keep: impl FnMut(&[BitSet], usize) -> bool) -> bool {
+        let removed: Vec<usize> = domains[variable.0].iter().filter(|&index| !keep(domains, index)).collect();
+        removed.iter().for_each(|&index| {
+            domains[variable.0].remove(index);
+        });
+        !removed.is_empty()
+    }
+
+    // Narrows the domains with `constraint`, `None` when a domain becomes empty or it is violated
+    fn revise(&self, constraint: &Constraint<'a, T>, domains: &mut [BitSet]) -> Option<bool> {
+        let mut changed = false;
+
+        match constraint {
+            Constraint::AllDifferent(variables) => {
+                for &variable in variables {
+                    if let Some(value) = self.assigned(domains, variable) {
+                        for &other in variables.iter().filter(|&&other| other != variable) {
+                            changed |= self.retain(domains, other, |_, index| &self.values[other.0][index] != value);
+                        }
+                    }
+                }
+            }
+            Constraint::Relation(a, b, relation) => {
+                let supported = |domains: &[BitSet], b: Var, value: &T, flipped: bool| {
+                    domains[b.0].iter().any(|index| {
+                        let other = &self.values[b.0][index];
+                        if flipped { relation(other, value) } else { relation(value, other) }
+                    })
+                };
+
+                changed |= self.retain(domains, *a, |domains, index| supported(domains, *b, &self.values[a.0][index], false));
+                changed |= self.retain(domains, *b, |domains, index| supported(domains, *a, &self.values[b.0][index], true));
+            }
+            Constraint::Custom(variables, predicate) => {
+                let unassigned: Vec<usize> = (0..variables.len())
+                    .filter(|&i| self.assigned(domains, variables[i]).is_none())
+                    .collect();
+
+                match unassigned[..] {
+                    [] => {
+                        let values: Vec<&T> = variables.iter().map(|&variable| self.assigned(domains, variable).unwrap()).collect();
+                        if !predicate(&values) {
+                            return None;
+                        }
+                    }
+                    [i] => {
+                        let mut values: Vec<&T> = variables.iter().map(|&variable| {
+                            self.assigned(domains, variable).unwrap_or(&self.values[variable.0][0])
+                        }).collect();
+                        changed |= self.retain(domains, variables[i], |_, index| {
+                            values[i] = &self.values[variables[i].0][index];
+                            predicate(&values)
+                        });
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        let empty = domains.iter().any(BitSet::is_empty);
+        (!empty).then_some(changed)
+    }
+
+    // Narrows the domains until no constraint changes them, false when one has no value left
+    fn propagate(&self, domains: &mut [BitSet]) -> bool {
+        // Revising only finds the domains it empties, not the ones empty from the start
+        if domains.iter().any(BitSet::is_empty) {
+            return false;
+        }
+
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for constraint in &self.constraints {
+                match self.revise(constraint, domains) {
+                    None => return false,
+                    Some(revised) => changed |= revised,
+                }
+            }
+        }
+        true
+    }
+
+    fn search(&self, mut domains: Vec<BitSet>, solutions: &mut Vec<Vec<usize>>, limit: usize) {
+        if !self.propagate(&mut domains) {
+            return;
+        }
+
+        let undecided = (0..domains.len())
+            .filter(|&variable| domains[variable].len() > 1)
+            .min_by_key(|&variable| domains[variable].len());
+
+        match undecided {
+            None => solutions.push(domains.iter().map(|domain| domain.iter().next().unwrap()).collect()),
+            Some(variable) => {
+                for index in domains[variable].iter() {
+                    let mut guess = domains.clone();
+                    guess[variable] = [index].into_iter().collect();
+                    self.search(guess, solutions, limit);
+
+                    if solutions.len() >= limit {
+                        return;
+                    }
+                }
+            }
+        }
+    }
+
+    fn solve_indices(&self, limit: usize) -> Vec<Vec<&T>> {
+        let mut solutions = vec![];
+        self.search(self.initial_domains(), &mut solutions, limit);
+
+        solutions
+            .into_iter()
+            .map(|indices| indices.into_iter().enumerate().map(|(variable, index)| &self.values[variable][index]).collect())
+            .collect()
+    }
+
+    /// Values of the variables, in the order they were added, in the first solution found.
+    pub fn solve(&self) -> Option<Vec<&T>> {
+        self.solve_indices(1).pop()
+    }
+
+    /// Every solution.
+    pub fn solutions(&self) -> Vec<Vec<&T>> {
+        self.solve_indices(usize::MAX)
+    }
+
+    /// Values each variable can still take once the constraints have been propagated, without any
+    /// guess, `None` if they can't all hold.
+    pub fn reduce(&self) -> Option<Vec<Vec<&T>>> {
+        let mut domains = self.initial_domains();
+        self.propagate(&mut domains).then(|| {
+            domains
+                .iter()
+                .enumerate()
+                .map(|(variable, domain)| domain.iter().map(|index| &self.values[variable][index]).collect())
+                .collect()
+        })
+    }
+}
+
+impl<'a, T: PartialEq> Default for Problem<'a, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Problem, Var};
+
+    #[test]
+    fn queens() {
+        let mut problem = Problem::new();
+        let columns: Vec<Var> = (0..8).map(|_| problem.variable(0..8i32)).collect();
+
+        for i in 0..8 {
+            for j in i + 1..8 {
+                let distance = (j - i) as i32;
+                problem.relation(columns[i], columns[j], move |a, b| a != b && (a - b).abs() != distance);
+            }
+        }
+
+        assert_eq!(problem.solutions().len(), 92);
+
+        let solution: Vec<i32> = problem.solve().unwrap().into_iter().copied().collect();
+        assert!((0..8).all(|i| (i + 1..8).all(|j| solution[i] != solution[j] && (solution[i] - solution[j]).abs() != (j - i) as i32)));
+    }
+
+    #[test]
+    fn deduction_without_guessing() {
+        // Allergens each in one ingredient, which must appear in every food listing it
+        let foods = [
+            (vec!["mxmxvkd", "kfcds", "sqjhc", "nhms"], vec!["dairy", "fish"]),
+            (vec!["trh", "fvjkl", "sbzzf", "mxmxvkd"], vec!["dairy"]),
+            (vec!["sqjhc", "fvjkl"], vec!["soy"]),
+            (vec!["sqjhc", "mxmxvkd", "sbzzf"], vec!["fish"]),
+        ];
+
+        let mut problem = Problem::new();
+        let allergens: Vec<Var> = ["dairy", "fish", "soy"]
+            .iter()
+            .map(|allergen| {
+                let candidates = foods[0].0.iter().chain(&foods[1].0).chain(&foods[2].0).copied();
+                let candidates: Vec<&str> = candidates
+                    .filter(|ingredient| foods.iter().all(|(ingredients, allergens)| {
+                        !allergens.contains(allergen) || ingredients.contains(ingredient)
+                    }))
+                    .collect();
+                problem.variable(candidates)
+            })
+            .collect();
+        problem.all_different(&allergens);
+
+        let reduced = problem.reduce().unwrap();
+        assert_eq!(reduced, vec![vec![&"mxmxvkd"], vec![&"sqjhc"], vec![&"fvjkl"]]);
+    }
+
+    #[test]
+    fn custom_constraints() {
+        // 3x3 magic squares
+        let mut problem = Problem::new();
+        let cells: Vec<Var> = (0..9).map(|_| problem.variable(1..=9)).collect();
+        problem.all_different(&cells);
+
+        let lines = [[0, 1, 2], [3, 4, 5], [6, 7, 8], [0, 3, 6], [1, 4, 7], [2, 5, 8], [0, 4, 8], [2, 4, 6]];
+        for line in lines {
+            let variables: Vec<Var> = line.iter().map(|&i| cells[i]).collect();
+            problem.constraint(&variables, |values| values.iter().copied().sum::<i32>() == 15);
+        }
+
+        let solutions = problem.solutions();
+        assert_eq!(solutions.len(), 8);
+        assert!(solutions.iter().all(|solution| *solution[4] == 5));
+
+        // Propagation alone doesn't find that there is no solution anymore
+        problem.constraint(&[cells[4]], |values| *values[0] != 5);
+        assert_eq!(problem.reduce().unwrap()[4].len(), 8);
+        assert_eq!(problem.solve(), None);
+    }
+
+    #[test]
+    fn no_variables() {
+        let problem: Problem<i32> = Problem::new();
+        assert_eq!(problem.solve(), Some(vec![]));
+        assert_eq!(problem.solutions(), vec![Vec::<&i32>::new()]);
+        assert_eq!(problem.reduce(), Some(vec![]));
+    }
+
+    #[test]
+    fn empty_domain() {
+        let mut problem = Problem::new();
+        problem.variable(1..=3);
+        problem.variable(vec![]);
+
+        assert_eq!(problem.solve(), None);
+        assert!(problem.solutions().is_empty());
+        assert_eq!(problem.reduce(), None);
+    }
+}
@@ -0,0 +1,227 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Shape of the sequence `initial, step(initial), step(step(initial))...` of a deterministic state machine:
+/// after `prefix` states, the same `length` states repeat forever.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Cycle {
+    pub prefix: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    /// Smallest step count reaching the same state as `n` steps.
+    pub fn reduce(&self, n: usize) -> usize {
+        if n < self.prefix {
+            n
+        } else {
+            self.prefix + (n - self.prefix) % self.length
+        }
+    }
+}
+
+/// Result of [`jump_to`] and its variants: the cycle found and the state reached after `n` steps.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Jump<S> {
+    pub prefix: usize,
+    pub length: usize,
+    pub state: S,
+}
+
+/// Finds the cycle by remembering every state seen. Fast, but keeps all of them in memory.
+///
+/// Also returns the states seen, in order, up to the one where the cycle starts again (excluded).
+pub fn find_cycle<S, F>(initial: S, mut step: F) -> (Cycle, Vec<S>)
+    where S: Clone + Hash + Eq,
+          F: FnMut(&S) -> S {
+    let mut seen: HashMap<S, usize> = HashMap::new();
+    let mut states = vec![];
+    let mut state = initial;
+
+    loop {
+        if let Some(&prefix) = seen.get(&state) {
+            let cycle = Cycle {
+                prefix,
+                length: states.len() - prefix,
+            };
+            return (cycle, states);
+        }
+
+        let next = step(&state);
+        seen.insert(state.clone(), states.len());
+        states.push(state);
+        state = next;
+    }
+}
+
+/// Finds the cycle with Brent's algorithm, keeping only two states in memory.
+pub fn find_cycle_brent<S, F>(initial: &S, mut step: F) -> Cycle
+    where S: Clone + PartialEq,
+          F: FnMut(&S) -> S {
+    // Find the cycle length by looking for the hare from tortoise positions at powers of two
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(initial);
+
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = step(&hare);
+        length += 1;
+    }
+
+    // Then the prefix, with the hare `length` steps ahead of the tortoise
+    let mut tortoise = initial.clone();
+    let mut hare = initial.clone();
+    for _ in 0..length {
+        hare = step(&hare);
+    }
+
+    let mut prefix = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        prefix += 1;
+    }
+
+    Cycle {
+        prefix,
+        length,
+    }
+}
+
+/// Finds the cycle with Floyd's tortoise and hare algorithm, keeping only two states in memory.
+pub fn find_cycle_floyd<S, F>(initial: &S, mut step: F) -> Cycle
+    where S: Clone + PartialEq,
+          F: FnMut(&S) -> S {
+    // The hare moves twice as fast, they meet somewhere in the cycle
+    let mut tortoise = step(initial);
+    let mut hare = step(&tortoise);
+
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        let half_way = step(&hare);
+        hare = step(&half_way);
+    }
+
+    // Their distance is now a multiple of the cycle length: moving both at the same speed,
+    // one of them from the start, they meet at the start of the cycle
+    let mut tortoise = initial.clone();
+    let mut prefix = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        prefix += 1;
+    }
+
+    let mut length = 1;
+    let mut hare = step(&tortoise);
+    while tortoise != hare {
+        hare = step(&hare);
+        length += 1;
+    }
+
+    Cycle {
+        prefix,
+        length,
+    }
+}
+
+/// State after `n` steps, found without running them all, see [`find_cycle`].
+pub fn jump_to<S, F>(initial: S, step: F, n: usize) -> Jump<S>
+    where S: Clone + Hash + Eq,
+          F: FnMut(&S) -> S {
+    let (cycle, mut states) = find_cycle(initial, step);
+
+    Jump {
+        prefix: cycle.prefix,
+        length: cycle.length,
+        state: states.swap_remove(cycle.reduce(n)),
+    }
+}
+
+/// State after `n` steps, found without running them all, see [`find_cycle_brent`].
+pub fn jump_to_brent<S, F>(initial: S, mut step: F, n: usize) -> Jump<S>
+    where S: Clone + PartialEq,
+          F: FnMut(&S) -> S {
+    let cycle = find_cycle_brent(&initial, &mut step);
+    replay(initial, step, cycle, n)
+}
+
+/// State after `n` steps, found without running them all, see [`find_cycle_floyd`].
+pub fn jump_to_floyd<S, F>(initial: S, mut step: F, n: usize) -> Jump<S>
+    where S: Clone + PartialEq,
+          F: FnMut(&S) -> S {
+    let cycle = find_cycle_floyd(&initial, &mut step);
+    replay(initial, step, cycle, n)
+}
+
+fn replay<S, F>(initial: S, mut step: F, cycle: Cycle, n: usize) -> Jump<S>
+    where F: FnMut(&S) -> S {
+    let state = (0..cycle.reduce(n)).fold(initial, |state, _| step(&state));
+
+    Jump {
+        prefix: cycle.prefix,
+        length: cycle.length,
+        state,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Cycle, find_cycle, find_cycle_brent, find_cycle_floyd, jump_to, jump_to_brent, jump_to_floyd};
+
+    // 0 -> 1 -> 2 -> 3 -> 4 -> 5 -> 6 -> 7 -> 3 -> ...
+    fn step(state: &u32) -> u32 {
+        if *state < 3 {
+            state + 1
+        } else {
+            3 + (state - 2) % 5
+        }
+    }
+
+    fn brute_force(n: usize) -> u32 {
+        (0..n).fold(0, |state, _| step(&state))
+    }
+
+    #[test]
+    fn detection() {
+        let expected = Cycle { prefix: 3, length: 5 };
+
+        let (cycle, states) = find_cycle(0, step);
+        assert_eq!(cycle, expected);
+        assert_eq!(states, vec![0, 1, 2, 3, 4, 5, 6, 7]);
+
+        assert_eq!(find_cycle_brent(&0, step), expected);
+        assert_eq!(find_cycle_floyd(&0, step), expected);
+    }
+
+    #[test]
+    fn no_prefix() {
+        let expected = Cycle { prefix: 0, length: 7 };
+        let step = |state: &u32| (state + 3) % 7;
+
+        assert_eq!(find_cycle(0, step).0, expected);
+        assert_eq!(find_cycle_brent(&0, step), expected);
+        assert_eq!(find_cycle_floyd(&0, step), expected);
+        assert_eq!(find_cycle_floyd(&0, |state: &u32| *state), Cycle { prefix: 0, length: 1 });
+    }
+
+    #[test]
+    fn jump() {
+        for n in 0..50 {
+            let expected = brute_force(n);
+            assert_eq!(jump_to(0, step, n).state, expected);
+            assert_eq!(jump_to_brent(0, step, n).state, expected);
+            assert_eq!(jump_to_floyd(0, step, n).state, expected);
+        }
+
+        // (1_000_000_000 - 3) % 5 == 2: 2 steps after the start of the cycle
+        let jump = jump_to(0, step, 1_000_000_000);
+        assert_eq!((jump.prefix, jump.length, jump.state), (3, 5, 5));
+    }
+}
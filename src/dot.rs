@@ -0,0 +1,296 @@
+use std::collections::HashMap;
+use std::fmt::{Display, Write};
+
+use crate::graph::Graph;
+
+/// Graphviz DOT text of a graph, with optional highlights. Render it with e.g. `dot -Tsvg`.
+#[derive(Clone, Debug)]
+pub struct Dot {
+    directed: bool,
+    nodes: Vec<String>,
+    edges: Vec<(String, String, Option<String>)>,
+    start: Option<String>,
+    end: Option<String>,
+    path: Vec<String>,
+    cycle: Vec<String>,
+}
+
+impl Dot {
+    fn new(directed: bool) -> Self {
+        Self {
+            directed,
+            nodes: vec![],
+            edges: vec![],
+            start: None,
+            end: None,
+            path: vec![],
+            cycle: vec![],
+        }
+    }
+
+    fn add_node(&mut self, name: String) {
+        if !self.nodes.contains(&name) {
+            self.nodes.push(name);
+        }
+    }
+
+    fn add_edge(&mut self, from: String, to: String, label: Option<String>) {
+        self.add_node(from.clone());
+        self.add_node(to.clone());
+        self.edges.push((from, to, label));
+    }
+
+    /// Edges with a weight other than 1 are labeled with it.
+    pub fn from_graph(graph: &Graph) -> Self {
+        let mut dot = Self::new(graph.is_directed());
+
+        for node in graph.nodes() {
+            dot.add_node(graph.name(node).to_string());
+        }
+        for node in graph.nodes() {
+            for &(neighbor, weight) in graph.edges(node) {
+                // Undirected edges are stored both ways, only keep one
+                if !graph.is_directed() && neighbor < node {
+                    continue;
+                }
+                let label = if weight == 1 { None } else { Some(weight.to_string()) };
+                dot.add_edge(graph.name(node).to_string(), graph.name(neighbor).to_string(), label);
+            }
+        }
+
+        dot
+    }
+
+    /// From a `node -> neighbors` map, nodes being sorted by name.
+    ///
+    /// For an undirected adjacency, where each edge is listed from both ends, only one is kept.
+    pub fn from_adjacency<K, C>(adjacency: &HashMap<K, C>, directed: bool) -> Self
+        where K: Display,
+              for<'a> &'a C: IntoIterator<Item=&'a K> {
+        let mut sorted: Vec<(String, &C)> = adjacency.iter().map(|(node, neighbors)| (node.to_string(), neighbors)).collect();
+        sorted.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        let mut dot = Self::new(directed);
+        // Undirected edges already added, waiting for their other end
+        let mut pending: HashMap<(String, String), usize> = HashMap::new();
+
+        for (node, _) in sorted.iter() {
+            dot.add_node(node.clone());
+        }
+        for (node, neighbors) in sorted {
+            for neighbor in neighbors {
+                let neighbor = neighbor.to_string();
+                if !directed {
+                    if let Some(count) = pending.get_mut(&(neighbor.clone(), node.clone())).filter(|count| **count > 0) {
+                        *count -= 1;
+                        continue;
+                    }
+                    *pending.entry((node.clone(), neighbor.clone())).or_default() += 1;
+                }
+                dot.add_edge(node.clone(), neighbor, None);
+            }
+        }
+
+        dot
+    }
+
+    pub fn from_edges<I, N>(edges: I, directed: bool) -> Self
+        where I: IntoIterator<Item=(N, N)>,
+              N: Display {
+        let mut dot = Self::new(directed);
+        for (from, to) in edges {
+            dot.add_edge(from.to_string(), to.to_string(), None);
+        }
+        dot
+    }
+
+    /// From `(from, to, label)` edges, e.g. the `(node, direction) -> node` map of a state machine.
+    pub fn from_labeled_edges<I, N, L>(edges: I, directed: bool) -> Self
+        where I: IntoIterator<Item=(N, N, L)>,
+              N: Display,
+              L: Display {
+        let mut dot = Self::new(directed);
+        for (from, to, label) in edges {
+            dot.add_edge(from.to_string(), to.to_string(), Some(label.to_string()));
+        }
+        dot
+    }
+
+    pub fn start<N: Display>(self, node: N) -> Self {
+        Self {
+            start: Some(node.to_string()),
+            ..self
+        }
+    }
+
+    pub fn end<N: Display>(self, node: N) -> Self {
+        Self {
+            end: Some(node.to_string()),
+            ..self
+        }
+    }
+
+    /// Highlights the nodes of `path` and the edges between consecutive ones.
+    pub fn path<I: IntoIterator<Item=N>, N: Display>(self, path: I) -> Self {
+        Self {
+            path: path.into_iter().map(|node| node.to_string()).collect(),
+            ..self
+        }
+    }
+
+    /// Highlights the nodes of `cycle` and the edges between consecutive ones, last to first included.
+    pub fn cycle<I: IntoIterator<Item=N>, N: Display>(self, cycle: I) -> Self {
+        Self {
+            cycle: cycle.into_iter().map(|node| node.to_string()).collect(),
+            ..self
+        }
+    }
+
+    fn highlighted(&self, nodes: &[String], closed: bool, from: &str, to: &str) -> bool {
+        let mut steps: Vec<(&String, &String)> = nodes.iter().zip(nodes.iter().skip(1)).collect();
+        if closed && !nodes.is_empty() {
+            steps.push((nodes.last().unwrap(), &nodes[0]));
+        }
+
+        steps.into_iter().any(|(a, b)| {
+            (a == from && b == to) || (!self.directed && a == to && b == from)
+        })
+    }
+
+    pub fn render(&self) -> String {
+        let mut output = String::new();
+        let (keyword, arrow) = if self.directed { ("digraph", "->") } else { ("graph", "--") };
+        writeln!(output, "{keyword} G {{").unwrap();
+
+        for node in self.nodes.iter() {
+            let mut attributes = vec![];
+            if self.start.as_ref() == Some(node) {
+                attributes.push("shape=doublecircle, style=filled, fillcolor=palegreen");
+            } else if self.end.as_ref() == Some(node) {
+                attributes.push("shape=doublecircle, style=filled, fillcolor=lightcoral");
+            }
+            if self.path.contains(node) {
+                attributes.push("color=red");
+            } else if self.cycle.contains(node) {
+                attributes.push("color=blue");
+            }
+
+            write!(output, "    {}", quote(node)).unwrap();
+            if !attributes.is_empty() {
+                write!(output, " [{}]", attributes.join(", ")).unwrap();
+            }
+            writeln!(output, ";").unwrap();
+        }
+
+        for (from, to, label) in self.edges.iter() {
+            let mut attributes = vec![];
+            if let Some(label) = label {
+                attributes.push(format!("label={}", quote(label)));
+            }
+            if self.highlighted(&self.path, false, from, to) {
+                attributes.push("color=red, penwidth=2".to_string());
+            } else if self.highlighted(&self.cycle, true, from, to) {
+                attributes.push("color=blue, penwidth=2".to_string());
+            }
+
+            write!(output, "    {} {arrow} {}", quote(from), quote(to)).unwrap();
+            if !attributes.is_empty() {
+                write!(output, " [{}]", attributes.join(", ")).unwrap();
+            }
+            writeln!(output, ";").unwrap();
+        }
+
+        output.push_str("}\n");
+        output
+    }
+}
+
+fn quote(id: &str) -> String {
+    format!("\"{}\"", id.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::graph::Graph;
+
+    use super::Dot;
+
+    #[test]
+    fn undirected_graph() {
+        let graph = Graph::parse_pairs("start-A\nA-end\nstart-b", "-", false).unwrap();
+
+        let dot = Dot::from_graph(&graph).start("start").end("end").path(["start", "A", "end"]);
+        assert_eq!(dot.render(), r#"graph G {
+    "start" [shape=doublecircle, style=filled, fillcolor=palegreen, color=red];
+    "A" [color=red];
+    "end" [shape=doublecircle, style=filled, fillcolor=lightcoral, color=red];
+    "b";
+    "start" -- "A" [color=red, penwidth=2];
+    "start" -- "b";
+    "A" -- "end" [color=red, penwidth=2];
+}
+"#);
+    }
+
+    #[test]
+    fn directed_weighted_graph() {
+        let mut graph = Graph::new_directed();
+        graph.add_weighted_edge("a", "b", 3);
+        graph.add_edge("b", "c");
+        graph.add_edge("c", "a");
+        graph.add_edge("c", "d");
+
+        let dot = Dot::from_graph(&graph).cycle(["a", "b", "c"]);
+        assert_eq!(dot.render(), r#"digraph G {
+    "a" [color=blue];
+    "b" [color=blue];
+    "c" [color=blue];
+    "d";
+    "a" -> "b" [label="3", color=blue, penwidth=2];
+    "b" -> "c" [color=blue, penwidth=2];
+    "c" -> "a" [color=blue, penwidth=2];
+    "c" -> "d";
+}
+"#);
+    }
+
+    #[test]
+    fn hash_map_sources() {
+        let adjacency: HashMap<String, Vec<String>> = HashMap::from([
+            ("b".to_string(), vec!["a".to_string()]),
+            ("a".to_string(), vec!["b".to_string(), "c".to_string()]),
+            ("c".to_string(), vec!["a".to_string()]),
+        ]);
+        assert_eq!(Dot::from_adjacency(&adjacency, false).render(), r#"graph G {
+    "a";
+    "b";
+    "c";
+    "a" -- "b";
+    "a" -- "c";
+}
+"#);
+
+        let mut network: Vec<((&str, char), &str)> = HashMap::from([
+            (("AAA", 'L'), "BBB"),
+            (("AAA", 'R'), "ZZZ"),
+        ]).into_iter().collect();
+        network.sort();
+        let dot = Dot::from_labeled_edges(network.into_iter().map(|((from, label), to)| (from, to, label)), true);
+        assert_eq!(dot.render(), r#"digraph G {
+    "AAA";
+    "BBB";
+    "ZZZ";
+    "AAA" -> "BBB" [label="L"];
+    "AAA" -> "ZZZ" [label="R"];
+}
+"#);
+    }
+
+    #[test]
+    fn escaping() {
+        let dot = Dot::from_edges([("say \"hi\"", "a\\b")], true);
+        assert!(dot.render().contains(r#""say \"hi\"" -> "a\\b";"#));
+    }
+}
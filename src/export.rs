@@ -0,0 +1,287 @@
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter, Write as _};
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use crate::CustomGrid;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub const BLACK: Rgb = Rgb(0, 0, 0);
+    pub const WHITE: Rgb = Rgb(255, 255, 255);
+    pub const RED: Rgb = Rgb(255, 0, 0);
+    pub const GREEN: Rgb = Rgb(0, 255, 0);
+    pub const BLUE: Rgb = Rgb(0, 0, 255);
+
+    fn hex(&self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.0, self.1, self.2)
+    }
+}
+
+#[derive(Debug)]
+pub enum ExportError {
+    IoError(io::Error),
+    PngError(png::EncodingError),
+    GifError(gif::EncodingError),
+    NoFrame,
+    TooLarge,
+    FrameSizeMismatch,
+}
+
+impl Display for ExportError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExportError::IoError(e) => write!(f, "I/O Error: {e}"),
+            ExportError::PngError(e) => write!(f, "PNG Error: {e}"),
+            ExportError::GifError(e) => write!(f, "GIF Error: {e}"),
+            ExportError::NoFrame => write!(f, "Nothing to export"),
+            ExportError::TooLarge => write!(f, "Image too large for the format"),
+            ExportError::FrameSizeMismatch => write!(f, "Frames don't all have the same size"),
+        }
+    }
+}
+
+impl From<io::Error> for ExportError {
+    fn from(e: io::Error) -> Self {
+        ExportError::IoError(e)
+    }
+}
+
+impl From<png::EncodingError> for ExportError {
+    fn from(e: png::EncodingError) -> Self {
+        ExportError::PngError(e)
+    }
+}
+
+impl From<gif::EncodingError> for ExportError {
+    fn from(e: gif::EncodingError) -> Self {
+        ExportError::GifError(e)
+    }
+}
+
+// Pixels of the grid, each cell being drawn as a `scale` x `scale` square: (width, height, pixels)
+fn rasterize<T, F: Fn(&T) -> Rgb>(grid: &CustomGrid<T>, color: &F, scale: usize) -> (usize, usize, Vec<Rgb>) {
+    let (width, height) = (grid.cols() * scale, grid.rows() * scale);
+    let mut pixels = Vec::with_capacity(width * height);
+
+    for row in 0..grid.rows() {
+        let line: Vec<Rgb> = (0..grid.cols())
+            .flat_map(|col| [color(grid.get(row, col).unwrap())].repeat(scale))
+            .collect();
+        for _ in 0..scale {
+            pixels.extend_from_slice(&line);
+        }
+    }
+
+    (width, height, pixels)
+}
+
+/// Writes the grid as a PNG image, each cell being a `scale` x `scale` square colored by `color`.
+pub fn write_png<T, F, W>(grid: &CustomGrid<T>, color: F, scale: usize, writer: W) -> Result<(), ExportError>
+    where F: Fn(&T) -> Rgb,
+          W: Write {
+    let (width, height, pixels) = rasterize(grid, &color, scale);
+    if width == 0 || height == 0 {
+        return Err(ExportError::NoFrame);
+    }
+
+    let mut encoder = png::Encoder::new(writer, width as u32, height as u32);
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+
+    let data: Vec<u8> = pixels.into_iter().flat_map(|Rgb(r, g, b)| [r, g, b]).collect();
+    let mut writer = encoder.write_header()?;
+    writer.write_image_data(&data)?;
+    writer.finish()?;
+
+    Ok(())
+}
+
+/// Writes the frames as an infinitely looping animated GIF, see [`write_png`].
+///
+/// Frames using at most 256 colors in total keep their exact colors, otherwise they are quantized.
+pub fn write_gif<T, F, W>(frames: &[CustomGrid<T>], color: F, scale: usize, delay_ms: u32, writer: W) -> Result<(), ExportError>
+    where F: Fn(&T) -> Rgb,
+          W: Write {
+    let Some(first) = frames.first() else {
+        return Err(ExportError::NoFrame);
+    };
+    if frames.iter().any(|frame| frame.size() != first.size()) {
+        return Err(ExportError::FrameSizeMismatch);
+    }
+
+    let images: Vec<Vec<Rgb>> = frames.iter().map(|frame| rasterize(frame, &color, scale).2).collect();
+    let (width, height) = (first.cols() * scale, first.rows() * scale);
+    let (Ok(width), Ok(height)) = (u16::try_from(width), u16::try_from(height)) else {
+        return Err(ExportError::TooLarge);
+    };
+    if width == 0 || height == 0 {
+        return Err(ExportError::NoFrame);
+    }
+
+    let mut palette: HashMap<Rgb, u8> = HashMap::new();
+    for &pixel in images.iter().flatten() {
+        if !palette.contains_key(&pixel) {
+            if palette.len() == 256 {
+                palette.clear();
+                break;
+            }
+            palette.insert(pixel, palette.len() as u8);
+        }
+    }
+
+    let mut global_palette = vec![0; palette.len() * 3];
+    for (&Rgb(r, g, b), &i) in palette.iter() {
+        global_palette[i as usize * 3..i as usize * 3 + 3].copy_from_slice(&[r, g, b]);
+    }
+
+    let mut encoder = gif::Encoder::new(writer, width, height, &global_palette)?;
+    encoder.set_repeat(gif::Repeat::Infinite)?;
+
+    for image in images {
+        let mut frame = if palette.is_empty() {
+            let rgb: Vec<u8> = image.into_iter().flat_map(|Rgb(r, g, b)| [r, g, b]).collect();
+            gif::Frame::from_rgb(width, height, &rgb)
+        } else {
+            let indexes: Vec<u8> = image.iter().map(|pixel| palette[pixel]).collect();
+            gif::Frame::from_indexed_pixels(width, height, &indexes, None)
+        };
+        // GIF delays are in hundredths of a second
+        frame.delay = (delay_ms / 10).min(u16::MAX as u32) as u16;
+        encoder.write_frame(&frame)?;
+    }
+
+    Ok(())
+}
+
+/// SVG drawing of the grid, each cell being a `scale` x `scale` square colored by `color`.
+pub fn to_svg<T, F: Fn(&T) -> Rgb>(grid: &CustomGrid<T>, color: F, scale: usize) -> String {
+    let mut svg = String::new();
+    writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" viewBox="0 0 {} {}" shape-rendering="crispEdges">"#,
+        grid.cols() * scale,
+        grid.rows() * scale,
+        grid.cols(),
+        grid.rows()
+    ).unwrap();
+
+    // One rectangle per run of same colored cells on a row
+    for row in 0..grid.rows() {
+        let mut col = 0;
+        while col < grid.cols() {
+            let run_color = color(grid.get(row, col).unwrap());
+            let run_start = col;
+            while col < grid.cols() && color(grid.get(row, col).unwrap()) == run_color {
+                col += 1;
+            }
+            writeln!(
+                svg,
+                r#"<rect x="{}" y="{}" width="{}" height="1" fill="{}"/>"#,
+                run_start,
+                row,
+                col - run_start,
+                run_color.hex()
+            ).unwrap();
+        }
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+pub fn save_png<T, F, P>(grid: &CustomGrid<T>, color: F, scale: usize, path: P) -> Result<(), ExportError>
+    where F: Fn(&T) -> Rgb,
+          P: AsRef<Path> {
+    write_png(grid, color, scale, BufWriter::new(File::create(path)?))
+}
+
+pub fn save_gif<T, F, P>(frames: &[CustomGrid<T>], color: F, scale: usize, delay_ms: u32, path: P) -> Result<(), ExportError>
+    where F: Fn(&T) -> Rgb,
+          P: AsRef<Path> {
+    write_gif(frames, color, scale, delay_ms, BufWriter::new(File::create(path)?))
+}
+
+pub fn save_svg<T, F, P>(grid: &CustomGrid<T>, color: F, scale: usize, path: P) -> Result<(), ExportError>
+    where F: Fn(&T) -> Rgb,
+          P: AsRef<Path> {
+    Ok(std::fs::write(path, to_svg(grid, color, scale))?)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{CustomGrid, input_to_grid};
+
+    use super::{ExportError, Rgb, to_svg, write_gif, write_png};
+
+    fn color(c: &char) -> Rgb {
+        match c {
+            '#' => Rgb::BLACK,
+            _ => Rgb::WHITE,
+        }
+    }
+
+    #[test]
+    fn png() {
+        let grid = input_to_grid::<char>("#.\n.#").unwrap();
+        let mut bytes = vec![];
+        write_png(&grid, color, 2, &mut bytes).unwrap();
+
+        let decoder = png::Decoder::new(bytes.as_slice());
+        let mut reader = decoder.read_info().unwrap();
+        let mut data = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut data).unwrap();
+
+        assert_eq!((info.width, info.height), (4, 4));
+        let pixel = |x: usize, y: usize| data[(y * 4 + x) * 3];
+        assert_eq!([pixel(0, 0), pixel(1, 1), pixel(2, 0), pixel(3, 3)], [0, 0, 255, 0]);
+    }
+
+    #[test]
+    fn gif() {
+        let mut frames: Vec<CustomGrid<char>> = vec![input_to_grid("#.\n..").unwrap()];
+        for _ in 0..3 {
+            let mut next = frames.last().unwrap().clone();
+            next.rotate_cw();
+            frames.push(next);
+        }
+
+        let mut bytes = vec![];
+        write_gif(&frames, color, 1, 100, &mut bytes).unwrap();
+
+        let mut options = gif::DecodeOptions::new();
+        options.set_color_output(gif::ColorOutput::RGBA);
+        let mut decoder = options.read_info(bytes.as_slice()).unwrap();
+        let mut black_pixels = vec![];
+        while let Some(frame) = decoder.read_next_frame().unwrap() {
+            assert_eq!(frame.delay, 10);
+            let black = frame.buffer.chunks(4).position(|rgba| rgba[..3] == [0, 0, 0]);
+            black_pixels.push(black.unwrap());
+        }
+        assert_eq!(black_pixels, vec![0, 1, 3, 2]);
+
+        let other_size = vec![input_to_grid::<char>("#").unwrap(), input_to_grid::<char>("##").unwrap()];
+        assert!(matches!(write_gif(&other_size, color, 1, 100, vec![]), Err(ExportError::FrameSizeMismatch)));
+        assert!(matches!(write_gif(&[], color, 1, 100, vec![]), Err(ExportError::NoFrame)));
+    }
+
+    #[test]
+    fn svg() {
+        let grid = input_to_grid::<char>("##.\n.#.").unwrap();
+
+        assert_eq!(
+            to_svg(&grid, color, 10),
+            r##"<svg xmlns="http://www.w3.org/2000/svg" width="30" height="20" viewBox="0 0 3 2" shape-rendering="crispEdges">
+<rect x="0" y="0" width="2" height="1" fill="#000000"/>
+<rect x="2" y="0" width="1" height="1" fill="#ffffff"/>
+<rect x="0" y="1" width="1" height="1" fill="#ffffff"/>
+<rect x="1" y="1" width="1" height="1" fill="#000000"/>
+<rect x="2" y="1" width="1" height="1" fill="#ffffff"/>
+</svg>
+"##
+        );
+    }
+}
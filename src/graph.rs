@@ -0,0 +1,444 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::ops::Range;
+
+use crate::parse::ParseError;
+
+pub type NodeId = usize;
+
+/// Weighted graph whose nodes are named, names being interned to [`NodeId`]s in order of appearance.
+///
+/// Edges keep their insertion order, so `AAA = (BBB, CCC)` gives `AAA` the edges `[BBB, CCC]`.
+#[derive(Clone, Debug)]
+pub struct Graph {
+    directed: bool,
+    names: Vec<String>,
+    ids: HashMap<String, NodeId>,
+    edges: Vec<Vec<(NodeId, u64)>>,
+}
+
+impl Graph {
+    pub fn new_directed() -> Self {
+        Self::new(true)
+    }
+
+    pub fn new_undirected() -> Self {
+        Self::new(false)
+    }
+
+    fn new(directed: bool) -> Self {
+        Self {
+            directed,
+            names: vec![],
+            ids: HashMap::new(),
+            edges: vec![],
+        }
+    }
+
+    pub fn is_directed(&self) -> bool {
+        self.directed
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    pub fn nodes(&self) -> Range<NodeId> {
+        0..self.names.len()
+    }
+
+    /// Id of the node named `name`, adding it if needed.
+    pub fn node(&mut self, name: &str) -> NodeId {
+        if let Some(&id) = self.ids.get(name) {
+            return id;
+        }
+
+        let id = self.names.len();
+        self.names.push(name.to_string());
+        self.ids.insert(name.to_string(), id);
+        self.edges.push(vec![]);
+        id
+    }
+
+    pub fn id(&self, name: &str) -> Option<NodeId> {
+        self.ids.get(name).copied()
+    }
+
+    pub fn name(&self, id: NodeId) -> &str {
+        &self.names[id]
+    }
+
+    /// Adds an edge of weight 1, both ways if the graph is undirected.
+    pub fn add_edge(&mut self, from: &str, to: &str) {
+        self.add_weighted_edge(from, to, 1)
+    }
+
+    pub fn add_weighted_edge(&mut self, from: &str, to: &str, weight: u64) {
+        let from = self.node(from);
+        let to = self.node(to);
+        self.add_edge_between(from, to, weight);
+    }
+
+    pub fn add_edge_between(&mut self, from: NodeId, to: NodeId, weight: u64) {
+        self.edges[from].push((to, weight));
+        if !self.directed && from != to {
+            self.edges[to].push((from, weight));
+        }
+    }
+
+    /// Outgoing edges of `id` with their weight, in insertion order.
+    pub fn edges(&self, id: NodeId) -> &[(NodeId, u64)] {
+        &self.edges[id]
+    }
+
+    pub fn neighbors(&self, id: NodeId) -> impl Iterator<Item=NodeId> + '_ {
+        self.edges[id].iter().map(|&(neighbor, _)| neighbor)
+    }
+
+    // Both sides of `separator` on each non-empty line, failing at the end of a line without it
+    fn split_lines<'a>(input: &'a str, separator: &str) -> Result<Vec<(&'a str, &'a str)>, ParseError> {
+        input
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(i, line)| {
+                line.split_once(separator).map(|(from, to)| (from.trim(), to.trim())).ok_or_else(|| {
+                    let column = line.trim_end().chars().count() + 1;
+                    ParseError::new(i + 1, column, line, &format!("`{separator}`"))
+                })
+            })
+            .collect()
+    }
+
+    /// Parses one edge per line, as `a-b` with `separator` being `"-"`.
+    pub fn parse_pairs(input: &str, separator: &str, directed: bool) -> Result<Self, ParseError> {
+        let mut graph = Self::new(directed);
+
+        for (from, to) in Self::split_lines(input, separator)? {
+            graph.add_edge(from, to);
+        }
+
+        Ok(graph)
+    }
+
+    /// Parses directed edges as `AAA = (BBB, CCC)` lines.
+    pub fn parse_branches(input: &str) -> Result<Self, ParseError> {
+        let mut graph = Self::new_directed();
+
+        for (from, to) in Self::split_lines(input, "=")? {
+            let to = to.trim_start_matches('(').trim_end_matches(')');
+            graph.node(from);
+            for to in to.split(',') {
+                graph.add_edge(from, to.trim());
+            }
+        }
+
+        Ok(graph)
+    }
+
+    /// Parses directed edges as `x -> y, z` lines, `separator` being `"->"`.
+    ///
+    /// A line without targets, like `x ->`, only adds the node.
+    pub fn parse_adjacency(input: &str, separator: &str) -> Result<Self, ParseError> {
+        let mut graph = Self::new_directed();
+
+        for (from, to) in Self::split_lines(input, separator)? {
+            graph.node(from);
+            for to in to.split(',').map(|to| to.trim()).filter(|to| !to.is_empty()) {
+                graph.add_edge(from, to);
+            }
+        }
+
+        Ok(graph)
+    }
+
+    /// Number of edges from `start` to every node, ignoring weights.
+    pub fn bfs(&self, start: NodeId) -> Vec<Option<usize>> {
+        let mut distances = vec![None; self.len()];
+        let mut queue = VecDeque::from([start]);
+        distances[start] = Some(0);
+
+        while let Some(node) = queue.pop_front() {
+            let distance = distances[node].unwrap();
+            for neighbor in self.neighbors(node) {
+                if distances[neighbor].is_none() {
+                    distances[neighbor] = Some(distance + 1);
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+
+        distances
+    }
+
+    /// Nodes reachable from `start`, in depth first pre-order.
+    pub fn dfs(&self, start: NodeId) -> Vec<NodeId> {
+        let mut visited = vec![false; self.len()];
+        let mut order = vec![];
+        let mut stack = vec![start];
+
+        while let Some(node) = stack.pop() {
+            if visited[node] {
+                continue;
+            }
+            visited[node] = true;
+            order.push(node);
+            // Reversed so that the first neighbor is visited first
+            stack.extend(self.neighbors(node).filter(|&neighbor| !visited[neighbor]).collect::<Vec<_>>().into_iter().rev());
+        }
+
+        order
+    }
+
+    /// Weight of the lightest path from `start` to every node.
+    pub fn dijkstra(&self, start: NodeId) -> Vec<Option<u64>> {
+        self.dijkstra_with_previous(start).0
+    }
+
+    /// Lightest path from `start` to `end`, both included, with its weight.
+    pub fn shortest_path(&self, start: NodeId, end: NodeId) -> Option<(u64, Vec<NodeId>)> {
+        let (distances, previous) = self.dijkstra_with_previous(start);
+        let distance = distances[end]?;
+
+        let mut path = vec![end];
+        while let Some(node) = previous[*path.last().unwrap()] {
+            path.push(node);
+        }
+        path.reverse();
+
+        Some((distance, path))
+    }
+
+    fn dijkstra_with_previous(&self, start: NodeId) -> (Vec<Option<u64>>, Vec<Option<NodeId>>) {
+        let mut distances = vec![None; self.len()];
+        let mut previous = vec![None; self.len()];
+        let mut heap = BinaryHeap::from([Reverse((0, start))]);
+        distances[start] = Some(0);
+
+        while let Some(Reverse((distance, node))) = heap.pop() {
+            if distances[node].is_some_and(|best| best < distance) {
+                continue;
+            }
+            for &(neighbor, weight) in self.edges(node) {
+                let candidate = distance + weight;
+                if distances[neighbor].is_none_or(|best| candidate < best) {
+                    distances[neighbor] = Some(candidate);
+                    previous[neighbor] = Some(node);
+                    heap.push(Reverse((candidate, neighbor)));
+                }
+            }
+        }
+
+        (distances, previous)
+    }
+
+    /// Nodes ordered so that every edge goes forward, `None` if the graph has a cycle.
+    pub fn topological_sort(&self) -> Option<Vec<NodeId>> {
+        let mut incoming = vec![0; self.len()];
+        for node in self.nodes() {
+            for neighbor in self.neighbors(node) {
+                incoming[neighbor] += 1;
+            }
+        }
+
+        let mut queue: VecDeque<NodeId> = self.nodes().filter(|&node| incoming[node] == 0).collect();
+        let mut order = vec![];
+
+        while let Some(node) = queue.pop_front() {
+            order.push(node);
+            for neighbor in self.neighbors(node) {
+                incoming[neighbor] -= 1;
+                if incoming[neighbor] == 0 {
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+
+        if order.len() == self.len() {
+            Some(order)
+        } else {
+            None
+        }
+    }
+
+    /// Strongly connected components (Kosaraju), each sorted by id.
+    ///
+    /// For an undirected graph, these are the connected components.
+    pub fn strongly_connected_components(&self) -> Vec<Vec<NodeId>> {
+        // First pass: nodes by increasing DFS finish time
+        let mut visited = vec![false; self.len()];
+        let mut finished = vec![];
+
+        for root in self.nodes() {
+            if visited[root] {
+                continue;
+            }
+            visited[root] = true;
+            let mut stack = vec![(root, 0)];
+
+            while let Some((node, next_edge)) = stack.pop() {
+                if let Some(&(neighbor, _)) = self.edges[node].get(next_edge) {
+                    stack.push((node, next_edge + 1));
+                    if !visited[neighbor] {
+                        visited[neighbor] = true;
+                        stack.push((neighbor, 0));
+                    }
+                } else {
+                    finished.push(node);
+                }
+            }
+        }
+
+        // Second pass: on the reversed graph, by decreasing finish time
+        let mut reversed = vec![vec![]; self.len()];
+        for node in self.nodes() {
+            for neighbor in self.neighbors(node) {
+                reversed[neighbor].push(node);
+            }
+        }
+
+        let mut assigned = vec![false; self.len()];
+        let mut components = vec![];
+
+        for &root in finished.iter().rev() {
+            if assigned[root] {
+                continue;
+            }
+            assigned[root] = true;
+            let mut component = vec![];
+            let mut stack = vec![root];
+
+            while let Some(node) = stack.pop() {
+                component.push(node);
+                for &neighbor in reversed[node].iter() {
+                    if !assigned[neighbor] {
+                        assigned[neighbor] = true;
+                        stack.push(neighbor);
+                    }
+                }
+            }
+
+            component.sort();
+            components.push(component);
+        }
+
+        components
+    }
+
+    /// Weight of the lightest path between every pair of nodes (Floyd-Warshall), indexed `[from][to]`.
+    pub fn all_pairs_shortest_paths(&self) -> Vec<Vec<Option<u64>>> {
+        let mut distances = vec![vec![None; self.len()]; self.len()];
+
+        for node in self.nodes() {
+            distances[node][node] = Some(0);
+            for &(neighbor, weight) in self.edges(node) {
+                if distances[node][neighbor].is_none_or(|best| weight < best) {
+                    distances[node][neighbor] = Some(weight);
+                }
+            }
+        }
+
+        for via in self.nodes() {
+            for from in self.nodes() {
+                let Some(to_via) = distances[from][via] else { continue };
+                for to in self.nodes() {
+                    let Some(from_via) = distances[via][to] else { continue };
+                    if distances[from][to].is_none_or(|best| to_via + from_via < best) {
+                        distances[from][to] = Some(to_via + from_via);
+                    }
+                }
+            }
+        }
+
+        distances
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Graph;
+    use crate::parse::ParseError;
+
+    #[test]
+    fn parse_pairs() {
+        let graph = Graph::parse_pairs("start-A\nA-b\nb-end\n", "-", false).unwrap();
+
+        assert_eq!(graph.len(), 4);
+        let a = graph.id("A").unwrap();
+        assert_eq!(graph.neighbors(a).map(|n| graph.name(n)).collect::<Vec<_>>(), vec!["start", "b"]);
+
+        let error = Graph::parse_pairs("start-A\n\nA b\n", "-", false).unwrap_err();
+        assert_eq!(error, ParseError::new(3, 4, "A b", "`-`"));
+    }
+
+    #[test]
+    fn parse_branches() {
+        let graph = Graph::parse_branches("AAA = (BBB, CCC)\nBBB = (DDD, EEE)\nCCC = (ZZZ, GGG)").unwrap();
+
+        let aaa = graph.id("AAA").unwrap();
+        assert_eq!(graph.neighbors(aaa).map(|n| graph.name(n)).collect::<Vec<_>>(), vec!["BBB", "CCC"]);
+        assert_eq!(graph.edges(graph.id("ZZZ").unwrap()), &[]);
+        assert!(graph.is_directed());
+    }
+
+    #[test]
+    fn parse_adjacency() {
+        let graph = Graph::parse_adjacency("x -> y, z\ny -> z\nz ->", "->").unwrap();
+
+        assert_eq!(graph.len(), 3);
+        assert_eq!(graph.neighbors(0).collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!(graph.topological_sort(), Some(vec![0, 1, 2]));
+    }
+
+    fn weighted() -> Graph {
+        let mut graph = Graph::new_directed();
+        graph.add_weighted_edge("a", "b", 7);
+        graph.add_weighted_edge("a", "c", 2);
+        graph.add_weighted_edge("c", "b", 3);
+        graph.add_weighted_edge("b", "d", 1);
+        graph.add_weighted_edge("d", "a", 10);
+        graph.node("e");
+        graph
+    }
+
+    #[test]
+    fn traversals() {
+        let graph = weighted();
+
+        assert_eq!(graph.bfs(0), vec![Some(0), Some(1), Some(1), Some(2), None]);
+        assert_eq!(graph.dfs(0), vec![0, 1, 3, 2]);
+    }
+
+    #[test]
+    fn shortest_paths() {
+        let graph = weighted();
+
+        assert_eq!(graph.dijkstra(0), vec![Some(0), Some(5), Some(2), Some(6), None]);
+        assert_eq!(graph.shortest_path(0, 3), Some((6, vec![0, 2, 1, 3])));
+        assert_eq!(graph.shortest_path(0, 4), None);
+
+        let all_pairs = graph.all_pairs_shortest_paths();
+        for from in graph.nodes() {
+            assert_eq!(all_pairs[from], graph.dijkstra(from));
+        }
+    }
+
+    #[test]
+    fn components() {
+        let graph = weighted();
+
+        assert_eq!(graph.topological_sort(), None);
+        let mut components = graph.strongly_connected_components();
+        components.sort();
+        assert_eq!(components, vec![vec![0, 1, 2, 3], vec![4]]);
+
+        let graph = Graph::parse_pairs("a-b\nc-d\nd-e", "-", false).unwrap();
+        let mut components = graph.strongly_connected_components();
+        components.sort();
+        assert_eq!(components, vec![vec![0, 1], vec![2, 3, 4]]);
+    }
+}
@@ -0,0 +1,278 @@
+use std::fmt::{Debug, Display, Formatter};
+use std::mem;
+
+use grid::{Grid, Order};
+
+use crate::CustomGrid;
+
+// Whole-grid transforms. Unlike their `grid::Grid` counterparts, they always leave the grid in
+// row-major order, so iterating over the underlying data keeps following rows.
+impl<T> CustomGrid<T> {
+    /// Rotates the grid 90° clockwise.
+    pub fn rotate_cw(&mut self) {
+        let rows = self.rows();
+        self.remap(self.cols(), rows, |row, col| (rows - 1 - col, row));
+    }
+
+    /// Rotates the grid 90° counter-clockwise.
+    pub fn rotate_ccw(&mut self) {
+        let cols = self.cols();
+        self.remap(cols, self.rows(), |row, col| (col, cols - 1 - row));
+    }
+
+    /// Mirrors the grid horizontally: the first column becomes the last one.
+    pub fn flip_h(&mut self) {
+        let cols = self.cols();
+        self.remap(self.rows(), cols, |row, col| (row, cols - 1 - col));
+    }
+
+    /// Mirrors the grid vertically: the first row becomes the last one.
+    pub fn flip_v(&mut self) {
+        let rows = self.rows();
+        self.remap(rows, self.cols(), |row, col| (rows - 1 - row, col));
+    }
+
+    /// Swaps rows and columns.
+    pub fn transpose(&mut self) {
+        self.remap(self.cols(), self.rows(), |row, col| (col, row));
+    }
+
+    /// Value at `(row, col)` of the grid repeated infinitely in every direction.
+    ///
+    /// Returns `None` if the grid is empty.
+    pub fn get_wrapped(&self, row: isize, col: isize) -> Option<&T> {
+        if self.rows() == 0 || self.cols() == 0 {
+            return None;
+        }
+
+        let row = row.rem_euclid(self.rows() as isize) as usize;
+        let col = col.rem_euclid(self.cols() as isize) as usize;
+        self.0.get(row, col)
+    }
+
+    /// Borrowed view of the `rows` x `cols` rectangle whose top left corner is `(row, col)`.
+    ///
+    /// Returns `None` if the rectangle doesn't fit in the grid.
+    pub fn view(&self, row: usize, col: usize, rows: usize, cols: usize) -> Option<GridView<'_, T>> {
+        if row.checked_add(rows)? > self.rows() || col.checked_add(cols)? > self.cols() {
+            return None;
+        }
+
+        Some(GridView {
+            grid: self,
+            row,
+            col,
+            rows,
+            cols,
+        })
+    }
+
+    pub fn row_view(&self, row: usize) -> Option<GridView<'_, T>> {
+        self.view(row, 0, 1, self.cols())
+    }
+
+    pub fn col_view(&self, col: usize) -> Option<GridView<'_, T>> {
+        self.view(0, col, self.rows(), 1)
+    }
+
+    pub(crate) fn into_row_major_vec(self) -> Vec<T> {
+        match self.0.order() {
+            Order::RowMajor => self.0.into_vec(),
+            Order::ColumnMajor => {
+                let mut grid = self;
+                let (rows, cols) = grid.size();
+                grid.remap(rows, cols, |row, col| (row, col));
+                grid.0.into_vec()
+            }
+        }
+    }
+
+    // Rebuilds a `rows` x `cols` grid, taking each cell at the position returned by `source`
+    fn remap<F: Fn(usize, usize) -> (usize, usize)>(&mut self, rows: usize, cols: usize, source: F) {
+        let old = mem::replace(&mut self.0, Grid::from_vec(vec![], 0));
+        let (old_rows, old_cols) = old.size();
+        let index = |row: usize, col: usize| match old.order() {
+            Order::RowMajor => row * old_cols + col,
+            Order::ColumnMajor => col * old_rows + row,
+        };
+        let indexes: Vec<usize> = (0..rows * cols)
+            .map(|i| {
+                let (source_row, source_col) = source(i / cols, i % cols);
+                index(source_row, source_col)
+            })
+            .collect();
+
+        let mut data: Vec<Option<T>> = old.into_vec().into_iter().map(Some).collect();
+        let data: Vec<T> = indexes
+            .into_iter()
+            .map(|i| data[i].take().expect("cell moved twice"))
+            .collect();
+
+        self.0 = Grid::from_vec(data, cols);
+    }
+}
+
+/// Borrowed rectangle of a [`CustomGrid`], indexed relatively to its top left corner.
+pub struct GridView<'a, T> {
+    grid: &'a CustomGrid<T>,
+    row: usize,
+    col: usize,
+    rows: usize,
+    cols: usize,
+}
+
+impl<'a, T> GridView<'a, T> {
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> Option<&'a T> {
+        if row >= self.rows || col >= self.cols {
+            None
+        } else {
+            self.grid.get(self.row + row, self.col + col)
+        }
+    }
+
+    /// Values in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item=&'a T> + '_ {
+        self.indexed_iter().map(|(_, value)| value)
+    }
+
+    pub fn indexed_iter(&self) -> impl Iterator<Item=((usize, usize), &'a T)> + '_ {
+        (0..self.rows)
+            .flat_map(move |row| (0..self.cols).map(move |col| (row, col)))
+            .map(|(row, col)| ((row, col), &self.grid.0[(self.row + row, self.col + col)]))
+    }
+}
+
+impl<'a, T: Clone> GridView<'a, T> {
+    pub fn to_grid(&self) -> CustomGrid<T> {
+        CustomGrid(Grid::from_vec(self.iter().cloned().collect(), self.cols))
+    }
+}
+
+impl<'a, T: PartialEq> PartialEq for GridView<'a, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.rows == other.rows && self.cols == other.cols && self.iter().eq(other.iter())
+    }
+}
+
+impl<'a, T: Display> Debug for GridView<'a, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for row in 0..self.rows {
+            for col in 0..self.cols {
+                write!(f, "{}", self.get(row, col).unwrap())?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{CustomGrid, input_to_grid};
+
+    fn example() -> CustomGrid<char> {
+        input_to_grid("abcd\nefgh\nijkl").unwrap()
+    }
+
+    #[test]
+    fn rotate() {
+        let mut grid = example();
+
+        grid.rotate_cw();
+        assert_eq!(format!("{:?}", grid), "iea\njfb\nkgc\nlhd\n");
+
+        grid.rotate_ccw();
+        assert_eq!(grid, example());
+
+        grid.rotate_ccw();
+        assert_eq!(format!("{:?}", grid), "dhl\ncgk\nbfj\naei\n");
+
+        for _ in 0..3 {
+            grid.rotate_ccw();
+        }
+        assert_eq!(grid, example());
+    }
+
+    #[test]
+    fn flip() {
+        let mut grid = example();
+
+        grid.flip_h();
+        assert_eq!(format!("{:?}", grid), "dcba\nhgfe\nlkji\n");
+        grid.flip_h();
+        assert_eq!(grid, example());
+
+        grid.flip_v();
+        assert_eq!(format!("{:?}", grid), "ijkl\nefgh\nabcd\n");
+        grid.flip_v();
+        assert_eq!(grid, example());
+    }
+
+    #[test]
+    fn transpose() {
+        let mut grid = example();
+
+        grid.transpose();
+        assert_eq!(format!("{:?}", grid), "aei\nbfj\ncgk\ndhl\n");
+        grid.transpose();
+        assert_eq!(grid, example());
+
+        // A clockwise rotation is a transposition followed by an horizontal flip
+        let mut rotated = example();
+        rotated.rotate_cw();
+        grid.transpose();
+        grid.flip_h();
+        assert_eq!(grid, rotated);
+    }
+
+    #[test]
+    fn column_major_layout_is_normalized() {
+        let mut grid = example();
+        grid.0.transpose();
+        grid.transpose();
+
+        assert_eq!(grid, example());
+        assert_eq!(grid.clone().into_row_major_vec(), "abcdefghijkl".chars().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn views() {
+        let grid = example();
+
+        let view = grid.view(1, 1, 2, 2).unwrap();
+        assert_eq!(format!("{:?}", view), "fg\njk\n");
+        assert_eq!(view.get(1, 0), Some(&'j'));
+        assert_eq!(view.get(2, 0), None);
+        assert_eq!(view.to_grid(), input_to_grid("fg\njk").unwrap());
+        assert!(grid.view(2, 2, 2, 2).is_none());
+        assert!(grid.view(1, 0, usize::MAX, 1).is_none());
+        assert!(grid.view(0, usize::MAX, 1, 2).is_none());
+
+        assert_eq!(grid.row_view(1).unwrap().iter().collect::<String>(), "efgh");
+        assert_eq!(grid.col_view(3).unwrap().iter().collect::<String>(), "dhl");
+        assert!(grid.row_view(3).is_none());
+
+        assert_eq!(grid.view(0, 0, 3, 4).unwrap().to_grid(), example());
+    }
+
+    #[test]
+    fn wrapped() {
+        let grid = example();
+
+        assert_eq!(grid.get_wrapped(0, 0), Some(&'a'));
+        assert_eq!(grid.get_wrapped(3, 4), Some(&'a'));
+        assert_eq!(grid.get_wrapped(-1, -1), Some(&'l'));
+        assert_eq!(grid.get_wrapped(-4, 9), Some(&'j'));
+
+        let empty: CustomGrid<char> = input_to_grid("").unwrap();
+        assert_eq!(empty.get_wrapped(0, 0), None);
+    }
+}
@@ -0,0 +1,347 @@
+use std::cmp::{max, min};
+
+/// Non-empty range of integers, `from` included and `to` excluded.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Interval {
+    pub from: i64,
+    pub to: i64,
+}
+
+impl Interval {
+    pub fn new(from: i64, to: i64) -> Self {
+        assert!(from < to, "empty interval {from}..{to}");
+        Self {
+            from,
+            to,
+        }
+    }
+
+    /// Number of integers in the interval.
+    pub fn length(&self) -> u64 {
+        self.to.abs_diff(self.from)
+    }
+
+    pub fn contains(&self, value: i64) -> bool {
+        self.from <= value && value < self.to
+    }
+
+    pub fn intersects(&self, other: &Interval) -> bool {
+        self.from < other.to && other.from < self.to
+    }
+
+    pub fn intersection(&self, other: &Interval) -> Option<Interval> {
+        if self.intersects(other) {
+            Some(Interval::new(max(self.from, other.from), min(self.to, other.to)))
+        } else {
+            None
+        }
+    }
+
+    /// (intersection, what's left of `self` outside of `other`)
+    pub fn split(&self, other: &Interval) -> (Option<Interval>, Vec<Interval>) {
+        match self.intersection(other) {
+            None => (None, vec![*self]),
+            Some(intersection) => {
+                let mut remainder = vec![];
+                if self.from < intersection.from {
+                    remainder.push(Interval::new(self.from, intersection.from))
+                }
+                if self.to > intersection.to {
+                    remainder.push(Interval::new(intersection.to, self.to))
+                }
+                (Some(intersection), remainder)
+            }
+        }
+    }
+
+    pub fn shift(&self, offset: i64) -> Interval {
+        Interval::new(self.from + offset, self.to + offset)
+    }
+}
+
+/// Set of integers stored as sorted, disjoint and non-adjacent intervals.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct IntervalSet {
+    intervals: Vec<Interval>,
+}
+
+impl IntervalSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn intervals(&self) -> &[Interval] {
+        &self.intervals
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item=&Interval> {
+        self.intervals.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// Number of integers in the set.
+    pub fn length(&self) -> u64 {
+        self.intervals.iter().map(Interval::length).sum()
+    }
+
+    pub fn min(&self) -> Option<i64> {
+        self.intervals.first().map(|interval| interval.from)
+    }
+
+    pub fn max(&self) -> Option<i64> {
+        self.intervals.last().map(|interval| interval.to - 1)
+    }
+
+    pub fn contains(&self, value: i64) -> bool {
+        let i = self.intervals.partition_point(|interval| interval.to <= value);
+        self.intervals.get(i).is_some_and(|interval| interval.contains(value))
+    }
+
+    pub fn insert(&mut self, interval: Interval) {
+        self.intervals.push(interval);
+        self.normalize();
+    }
+
+    // Sorts and merges overlapping or adjacent intervals
+    fn normalize(&mut self) {
+        self.intervals.sort();
+        let mut merged: Vec<Interval> = Vec::with_capacity(self.intervals.len());
+        for interval in self.intervals.drain(..) {
+            match merged.last_mut() {
+                Some(last) if interval.from <= last.to => last.to = max(last.to, interval.to),
+                _ => merged.push(interval),
+            }
+        }
+        self.intervals = merged;
+    }
+
+    pub fn union(&self, other: &IntervalSet) -> IntervalSet {
+        self.intervals.iter().chain(other.intervals.iter()).copied().collect()
+    }
+
+    pub fn intersection(&self, other: &IntervalSet) -> IntervalSet {
+        let mut intervals = vec![];
+        let (mut i, mut j) = (0, 0);
+        while i < self.intervals.len() && j < other.intervals.len() {
+            let (a, b) = (&self.intervals[i], &other.intervals[j]);
+            if let Some(intersection) = a.intersection(b) {
+                intervals.push(intersection);
+            }
+            if a.to < b.to {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+
+        // Intersections of disjoint sorted intervals are already sorted and disjoint, but can be adjacent
+        intervals.into_iter().collect()
+    }
+
+    pub fn difference(&self, other: &IntervalSet) -> IntervalSet {
+        let mut intervals = vec![];
+        let mut j = 0;
+        for interval in self.intervals.iter() {
+            while j < other.intervals.len() && other.intervals[j].to <= interval.from {
+                j += 1;
+            }
+
+            let mut rest = Some(*interval);
+            for removed in other.intervals[j..].iter().take_while(|removed| removed.from < interval.to) {
+                let Some(current) = rest else { break };
+                let (_, remainder) = current.split(removed);
+                // Anything before `removed` is final, what's after may still be cut by the next ones
+                rest = None;
+                for piece in remainder {
+                    if piece.to <= removed.from {
+                        intervals.push(piece);
+                    } else {
+                        rest = Some(piece);
+                    }
+                }
+            }
+            intervals.extend(rest);
+        }
+
+        Self {
+            intervals,
+        }
+    }
+
+    /// Piecewise-linear mapping: values in `source` of a `(source, offset)` entry get `offset` added,
+    /// values outside of every source are kept as is.
+    ///
+    /// Values are mapped by the first entry containing them, e.g. the almanac maps of 2023-05.
+    pub fn map(&self, table: &[(Interval, i64)]) -> IntervalSet {
+        let (unmapped, mut mapped) = table
+            .iter()
+            .fold((self.intervals.clone(), vec![]), |(unmapped, mut mapped), (source, offset)| {
+                let unmapped = unmapped
+                    .into_iter()
+                    .flat_map(|interval| {
+                        let (intersection, remainder) = interval.split(source);
+                        if let Some(intersection) = intersection {
+                            mapped.push(intersection.shift(*offset));
+                        }
+                        remainder
+                    })
+                    .collect();
+                (unmapped, mapped)
+            });
+
+        mapped.extend(unmapped);
+        mapped.into_iter().collect()
+    }
+}
+
+impl FromIterator<Interval> for IntervalSet {
+    fn from_iter<I: IntoIterator<Item=Interval>>(iter: I) -> Self {
+        let mut set = Self {
+            intervals: iter.into_iter().collect(),
+        };
+        set.normalize();
+        set
+    }
+}
+
+impl<'a> IntoIterator for &'a IntervalSet {
+    type Item = &'a Interval;
+    type IntoIter = std::slice::Iter<'a, Interval>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.intervals.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use crate::testing::Random;
+
+    use super::{Interval, IntervalSet};
+
+    #[test]
+    fn interval_intersects() {
+        let interval = Interval::new(10, 15);
+
+        assert!(interval.intersects(&interval));
+        assert!(interval.intersects(&Interval::new(10, 11)));
+        assert!(interval.intersects(&Interval::new(11, 12)));
+        assert!(interval.intersects(&Interval::new(8, 12)));
+        assert!(interval.intersects(&Interval::new(12, 16)));
+        assert!(!interval.intersects(&Interval::new(15, 16)));
+        assert!(!interval.intersects(&Interval::new(9, 10)));
+        assert!(!interval.intersects(&Interval::new(16, 17)));
+        assert!(!interval.intersects(&Interval::new(8, 9)));
+    }
+
+    #[test]
+    fn interval_intersection() {
+        let interval = Interval::new(10, 15);
+
+        assert_eq!(interval.intersection(&Interval::new(10, 11)), Some(Interval::new(10, 11)));
+        assert_eq!(interval.intersection(&Interval::new(11, 12)), Some(Interval::new(11, 12)));
+        assert_eq!(interval.intersection(&Interval::new(8, 12)), Some(Interval::new(10, 12)));
+        assert_eq!(interval.intersection(&Interval::new(12, 16)), Some(Interval::new(12, 15)));
+        assert_eq!(interval.intersection(&Interval::new(15, 16)), None);
+        assert_eq!(interval.intersection(&Interval::new(9, 10)), None);
+    }
+
+    #[test]
+    fn interval_split() {
+        let interval = Interval::new(10, 15);
+
+        assert_eq!(interval.split(&Interval::new(10, 11)), (Some(Interval::new(10, 11)), vec![Interval::new(11, 15)]));
+        assert_eq!(interval.split(&Interval::new(11, 12)), (Some(Interval::new(11, 12)), vec![Interval::new(10, 11), Interval::new(12, 15)]));
+        assert_eq!(interval.split(&Interval::new(8, 12)), (Some(Interval::new(10, 12)), vec![Interval::new(12, 15)]));
+        assert_eq!(interval.split(&Interval::new(12, 16)), (Some(Interval::new(12, 15)), vec![Interval::new(10, 12)]));
+        assert_eq!(interval.split(&Interval::new(15, 16)), (None, vec![Interval::new(10, 15)]));
+        assert_eq!(interval.split(&Interval::new(0, 10)), (None, vec![Interval::new(10, 15)]));
+    }
+
+    #[test]
+    fn normalization() {
+        let set: IntervalSet = [Interval::new(5, 8), Interval::new(0, 2), Interval::new(2, 3), Interval::new(6, 10)]
+            .into_iter()
+            .collect();
+
+        assert_eq!(set.intervals(), &[Interval::new(0, 3), Interval::new(5, 10)]);
+        assert_eq!(set.length(), 8);
+        assert_eq!((set.min(), set.max()), (Some(0), Some(9)));
+    }
+
+    fn random_set(random: &mut Random) -> IntervalSet {
+        (0..random.below(5))
+            .map(|_| {
+                let from = random.range(-10..30);
+                Interval::new(from, from + random.range(1..11))
+            })
+            .collect()
+    }
+
+    fn brute_force(set: &IntervalSet) -> BTreeSet<i64> {
+        set.iter().flat_map(|interval| interval.from..interval.to).collect()
+    }
+
+    fn assert_normalized(set: &IntervalSet) {
+        for pair in set.intervals().windows(2) {
+            assert!(pair[0].to < pair[1].from, "{set:?} isn't normalized");
+        }
+    }
+
+    #[test]
+    fn set_operations_match_brute_force() {
+        let mut random = Random::new(1);
+
+        for _ in 0..1000 {
+            let (a, b) = (random_set(&mut random), random_set(&mut random));
+            let (brute_a, brute_b) = (brute_force(&a), brute_force(&b));
+
+            let union = a.union(&b);
+            let intersection = a.intersection(&b);
+            let difference = a.difference(&b);
+            for set in [&union, &intersection, &difference] {
+                assert_normalized(set);
+            }
+
+            assert_eq!(brute_force(&union), &brute_a | &brute_b);
+            assert_eq!(brute_force(&intersection), &brute_a & &brute_b);
+            assert_eq!(brute_force(&difference), &brute_a - &brute_b);
+            assert_eq!(a.length(), brute_a.len() as u64);
+            for value in -12..42 {
+                assert_eq!(a.contains(value), brute_a.contains(&value));
+            }
+        }
+    }
+
+    #[test]
+    fn map_matches_brute_force() {
+        let mut random = Random::new(2);
+
+        for _ in 0..1000 {
+            let set = random_set(&mut random);
+            let table: Vec<(Interval, i64)> = random_set(&mut random)
+                .iter()
+                .map(|&source| (source, random.range(-30..30)))
+                .collect();
+
+            let expected: BTreeSet<i64> = brute_force(&set)
+                .into_iter()
+                .map(|value| {
+                    table
+                        .iter()
+                        .find(|(source, _)| source.contains(value))
+                        .map_or(value, |(_, offset)| value + offset)
+                })
+                .collect();
+
+            let mapped = set.map(&table);
+            assert_normalized(&mapped);
+            assert_eq!(brute_force(&mapped), expected);
+        }
+    }
+}
@@ -0,0 +1,165 @@
+use std::marker::PhantomData;
+use std::str::FromStr;
+
+use crate::parse::ParseError;
+
+/// Every integer in `text`, whatever is around them, without allocating.
+///
+/// A `-` or `+` right before a digit is the sign of the number, unless it's one of the
+/// [`Ints::separators`] or `T` can't be negative, so that `x=2-4` gives `2` and `4` for unsigned
+/// numbers. Anything else that isn't a digit separates numbers.
+///
+/// # Panics
+///
+/// When iterating, on a number that doesn't fit in `T`, see [`Ints::try_next`] to get an error instead.
+pub fn ints<T: FromStr>(text: &str) -> Ints<'_, T> {
+    Ints {
+        text,
+        position: 0,
+        separators: &[],
+        negative: "-1".parse::<T>().is_ok(),
+        phantom: PhantomData,
+    }
+}
+
+/// The `N` integers of `text`, see [`ints`].
+pub fn ints_n<T: FromStr, const N: usize>(text: &str) -> Result<[T; N], ParseError> {
+    ints(text).collect_array()
+}
+
+pub struct Ints<'a, T> {
+    text: &'a str,
+    position: usize,
+    separators: &'a [char],
+    // Whether `T` has negative numbers, `-` being a separator otherwise
+    negative: bool,
+    phantom: PhantomData<T>,
+}
+
+impl<'a, T: FromStr> Ints<'a, T> {
+    /// Characters that are never signs, e.g. `-` for ranges like `2-4,6-8`.
+    pub fn separators(self, separators: &'a [char]) -> Self {
+        Self {
+            separators,
+            ..self
+        }
+    }
+
+    /// The next `N` integers, with an error if there are less or more of them.
+    pub fn collect_array<const N: usize>(mut self) -> Result<[T; N], ParseError> {
+        let mut found = 0;
+        let mut error = None;
+        let numbers: [Option<T>; N] = std::array::from_fn(|_| match self.try_next() {
+            Some(Ok(number)) => {
+                found += 1;
+                Some(number)
+            }
+            Some(Err(e)) => {
+                error.get_or_insert(e);
+                None
+            }
+            None => None,
+        });
+
+        if let Some(error) = error {
+            return Err(error);
+        }
+        if found < N {
+            let error = ParseError::at(self.text, "", &format!("{N} integers, found {found}"));
+            return Err(error);
+        }
+        if let Some(start) = self.next_start() {
+            let error = ParseError::at(self.text, &self.text[start..], &format!("only {N} integers"));
+            return Err(error);
+        }
+
+        Ok(numbers.map(|number| number.unwrap()))
+    }
+
+    fn is_sign(&self, i: usize) -> bool {
+        let bytes = self.text.as_bytes();
+        (bytes[i] == b'+' || (bytes[i] == b'-' && self.negative))
+            && bytes.get(i + 1).is_some_and(u8::is_ascii_digit)
+            && !self.separators.contains(&(bytes[i] as char))
+    }
+
+    // Start of the next number, sign included, without moving forward
+    fn next_start(&self) -> Option<usize> {
+        (self.position..self.text.len()).find(|&i| self.text.as_bytes()[i].is_ascii_digit() || self.is_sign(i))
+    }
+
+    // Start and end of the next number, moving past it
+    fn next_number(&mut self) -> Option<(usize, usize)> {
+        let start = self.next_start()?;
+        let bytes = self.text.as_bytes();
+
+        let mut end = start + 1;
+        while end < bytes.len() && bytes[end].is_ascii_digit() {
+            end += 1;
+        }
+        self.position = end;
+
+        Some((start, end))
+    }
+
+    /// Next integer, with an error if it doesn't fit in `T`.
+    pub fn try_next(&mut self) -> Option<Result<T, ParseError>> {
+        let (start, end) = self.next_number()?;
+
+        Some(self.text[start..end].parse().map_err(|_| {
+            let expected = format!("a number that fits in {}", std::any::type_name::<T>());
+            ParseError::at(self.text, &self.text[start..], &expected)
+        }))
+    }
+}
+
+impl<'a, T: FromStr> Iterator for Ints<'a, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (start, end) = self.next_number()?;
+
+        match self.text[start..end].parse() {
+            Ok(number) => Some(number),
+            Err(_) => panic!("{} doesn't fit in {}", &self.text[start..end], std::any::type_name::<T>()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ints, ints_n};
+
+    #[test]
+    fn signs_and_separators() {
+        assert_eq!(ints::<i64>("p=0,4 v=-3,+3").collect::<Vec<_>>(), vec![0, 4, -3, 3]);
+        assert_eq!(ints::<u32>("Card  12: 41 48 | 83 86  6").collect::<Vec<_>>(), vec![12, 41, 48, 83, 86, 6]);
+        assert_eq!(ints::<i32>("a - b -- 7-").collect::<Vec<_>>(), vec![7]);
+        assert_eq!(ints::<i32>("x=20..30, y=-10..-5").collect::<Vec<_>>(), vec![20, 30, -10, -5]);
+        assert_eq!(ints::<u32>("").count(), 0);
+
+        assert_eq!(ints::<i32>("2-4,6-8").collect::<Vec<_>>(), vec![2, -4, 6, -8]);
+        assert_eq!(ints::<i32>("2-4,6-8").separators(&['-']).collect::<Vec<_>>(), vec![2, 4, 6, 8]);
+        assert_eq!(ints::<u32>("x=2-4, y=+3").collect::<Vec<_>>(), vec![2, 4, 3]);
+    }
+
+    #[test]
+    #[should_panic(expected = "doesn't fit in u8")]
+    fn too_large() {
+        ints::<u8>("1 300").for_each(drop);
+    }
+
+    #[test]
+    fn fixed_count() {
+        assert_eq!(ints_n::<i64, 4>("Sensor at x=2, y=18: beacon at x=-2, y=15"), Ok([2, 18, -2, 15]));
+
+        let error = ints_n::<i64, 3>("1 2").unwrap_err();
+        assert_eq!((error.column, error.expected.as_str()), (4, "3 integers, found 2"));
+
+        let error = ints_n::<i64, 2>("1 2 30").unwrap_err();
+        assert_eq!((error.column, error.expected.as_str()), (5, "only 2 integers"));
+
+        let error = ints_n::<u8, 3>("1 300 2").unwrap_err();
+        assert_eq!((error.column, error.expected.as_str()), (3, "a number that fits in u8"));
+    }
+}
@@ -4,25 +4,130 @@ use std::ops::{Deref, DerefMut};
 use std::process::{Command, Stdio};
 use std::str::FromStr;
 
+// Lets the code generated by `#[derive(AocParse)]` name this crate from inside it too
+extern crate self as advent_of_code;
+
 use clap::Parser;
 use grid::*;
 
+pub mod animation;
+pub mod automaton;
+pub mod axis_box;
+pub mod bitset;
+pub mod bytes;
+pub mod constraint;
+pub mod cycle;
+pub mod dot;
+pub mod export;
+pub mod graph;
+pub mod grid_transform;
+pub mod interval;
+pub mod ints;
+pub mod line_format;
+pub mod math;
+pub mod memo;
+pub mod parse;
+pub mod paths;
+pub mod render;
+pub mod sequence;
+pub mod sparse_grid;
+#[cfg(test)]
+mod testing;
+
+pub use aoc_derive::AocParse;
+pub use automaton::{Automaton, Neighborhood};
+pub use grid_transform::GridView;
+pub use parse::ParseError;
+pub use sparse_grid::SparseGrid;
+
+/// Reads the input of a day, normalized with [`normalize_input`].
 pub fn read_input(day: u8, year: u16) -> String {
     let cwd = env::current_dir().unwrap();
     let input_filepath = cwd.join("src").join("bin").join(format!("{year}-{day:02}/input.txt"));
-    fs::read_to_string(input_filepath).expect("Could not open input")
+    normalize_input(&fs::read_to_string(input_filepath).expect("Could not open input"))
 }
 
+/// Reads the example of a day, normalized with [`normalize_input`].
 pub fn read_example(day: u8, year: u16) -> String {
     let cwd = env::current_dir().unwrap();
     let input_filepath = cwd.join("src").join("bin").join(format!("{year}-{day:02}/example.txt"));
-    fs::read_to_string(input_filepath).expect("Could not open example")
+    normalize_input(&fs::read_to_string(input_filepath).expect("Could not open example"))
 }
 
+/// Reads the second example of a day, normalized with [`normalize_input`].
 pub fn read_example_2(day: u8, year: u16) -> String {
     let cwd = env::current_dir().unwrap();
     let input_filepath = cwd.join("src").join("bin").join(format!("{year}-{day:02}/example2.txt"));
-    fs::read_to_string(input_filepath).expect("Could not open example")
+    normalize_input(&fs::read_to_string(input_filepath).expect("Could not open example"))
+}
+
+/// Same input with `\n` line endings, blank lines emptied, and a single line ending at the end.
+///
+/// Spaces at the end of other lines are kept, as they can be part of a grid.
+pub fn normalize_input(input: &str) -> String {
+    let mut normalized = String::with_capacity(input.len() + 1);
+    for line in input.lines() {
+        if !line.trim().is_empty() {
+            normalized.push_str(line.strip_suffix('\r').unwrap_or(line));
+        }
+        normalized.push('\n');
+    }
+
+    let end = normalized.trim_end_matches('\n').len();
+    normalized.truncate(end);
+    if !normalized.is_empty() {
+        normalized.push('\n');
+    }
+    normalized
+}
+
+/// Parts of `input` separated by blank lines, whatever the line endings, without their last line ending.
+pub fn sections(input: &str) -> Sections<'_> {
+    Sections {
+        rest: input,
+    }
+}
+
+pub struct Sections<'a> {
+    rest: &'a str,
+}
+
+impl<'a> Sections<'a> {
+    // (first line without its line ending, what comes after it)
+    fn split_line(text: &'a str) -> (&'a str, &'a str) {
+        let (line, rest) = text.split_once('\n').unwrap_or((text, ""));
+        (line.strip_suffix('\r').unwrap_or(line), rest)
+    }
+}
+
+impl<'a> Iterator for Sections<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.rest.is_empty() {
+                return None;
+            }
+            let (line, rest) = Self::split_line(self.rest);
+            if !line.trim().is_empty() {
+                break;
+            }
+            self.rest = rest;
+        }
+
+        let start = self.rest;
+        let mut end = 0;
+        while !self.rest.is_empty() {
+            let (line, rest) = Self::split_line(self.rest);
+            if line.trim().is_empty() {
+                break;
+            }
+            end = start.len() - self.rest.len() + line.len();
+            self.rest = rest;
+        }
+
+        Some(&start[..end])
+    }
 }
 
 pub fn get_folder_path(day: u8, year: u16) -> String {
@@ -129,7 +234,7 @@ pub fn submit(day: u8, year: u16, part: u8, response: &str) -> Result<bool, Subm
     let output = Command::new("aoc")
         .args(args)
         .output()
-        .map_err(|e| SubmitError::IoError(e))?;
+        .map_err(SubmitError::IoError)?;
 
     let stdout = String::from_utf8(output.stdout).unwrap();
 
@@ -144,8 +249,30 @@ pub fn submit(day: u8, year: u16, part: u8, response: &str) -> Result<bool, Subm
     }
 }
 
-pub fn input_to_list<T: FromStr>(input: &str) -> Result<Vec<T>, <T as FromStr>::Err> {
-    input.lines().map(|line| line.trim().parse()).collect()
+pub fn input_to_list<T: FromStr>(input: &str) -> Result<Vec<T>, ParseError> where T::Err: Display {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            line.trim().parse().map_err(|e| {
+                let column = line.len() - line.trim_start().len() + 1;
+                ParseError::new(i + 1, column, line, &format!("{} ({e})", std::any::type_name::<T>()))
+            })
+        })
+        .collect()
+}
+
+/// Same as [`input_to_list`], for types parsed with a [`ParseError`] pointing in the line, such as
+/// the ones of `#[derive(AocParse)]`: it is moved to where the line is in the input.
+pub fn input_to_records<T: FromStr<Err=ParseError>>(input: &str) -> Result<Vec<T>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            let leading = line.chars().take_while(|c| c.is_whitespace()).count();
+            line.trim().parse().map_err(|e: ParseError| e.relocate(i + 1, leading, line))
+        })
+        .collect()
 }
 
 #[repr(transparent)]
@@ -165,6 +292,20 @@ impl<T> DerefMut for CustomGrid<T> {
     }
 }
 
+impl<T: Clone> Clone for CustomGrid<T> {
+    fn clone(&self) -> Self {
+        CustomGrid(self.0.clone())
+    }
+}
+
+impl<T: Eq> PartialEq for CustomGrid<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<T: Eq> Eq for CustomGrid<T> {}
+
 impl<T: Display> Debug for CustomGrid<T> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         for row in 0..self.0.rows() {
@@ -275,6 +416,23 @@ impl<T> CustomGrid<T> {
     pub fn down_mut(&mut self, row: usize, col: usize) -> Option<((usize, usize), &mut T)> {
         self.0.get_mut(row + 1, col).map(|val| ((row + 1, col), val))
     }
+
+    /// First position (in row-major order) whose value matches `predicate`.
+    pub fn position<P: Fn(&T) -> bool>(&self, predicate: P) -> Option<(usize, usize)> {
+        self.0
+            .indexed_iter()
+            .find(|(_, value)| predicate(value))
+            .map(|(position, _)| position)
+    }
+
+    /// Every position whose value matches `predicate`, in row-major order.
+    pub fn positions<P: Fn(&T) -> bool>(&self, predicate: P) -> Vec<(usize, usize)> {
+        self.0
+            .indexed_iter()
+            .filter(|(_, value)| predicate(value))
+            .map(|(position, _)| position)
+            .collect()
+    }
 }
 
 impl<T: Display> CustomGrid<T> {
@@ -289,15 +447,151 @@ impl<T: Display> CustomGrid<T> {
 }
 
 
-pub fn input_to_grid<T: FromStr>(input: &str) -> Result<CustomGrid<T>, <T as FromStr>::Err> {
-    let lines: Vec<&str> = input.lines().map(|line| line.trim()).collect();
-    let cols = lines[0].len();
+// Grid of the cells `read` pushes for each line, telling how many there were, which has to be the
+// same for every line. Only line endings are trimmed, spaces being cells like any other character.
+fn grid_of_lines<T, F>(input: &str, mut read: F) -> Result<CustomGrid<T>, ParseError>
+    where F: FnMut(usize, &str, &mut Vec<T>) -> Result<usize, ParseError> {
+    let mut data = vec![];
+    let mut cols = None;
+
+    for (i, line) in input.trim_end_matches(['\n', '\r']).lines().enumerate() {
+        let len = read(i + 1, line, &mut data)?;
+
+        match cols {
+            None => cols = Some(len),
+            Some(cols) if cols != len => {
+                let expected = format!("{cols} cells like the first line, found {len}");
+                return Err(ParseError::new(i + 1, cols.min(len) + 1, line, &expected));
+            }
+            Some(_) => {}
+        }
+    }
+
+    Ok(CustomGrid(Grid::from_vec(data, cols.unwrap_or(0))))
+}
+
+/// Grid of the characters of `input`, parsed with `FromStr` (without allocating for each of them).
+///
+/// Fails on a character that doesn't parse, or on a line not as long as the first one.
+pub fn input_to_grid<T: FromStr>(input: &str) -> Result<CustomGrid<T>, ParseError> where T::Err: Display {
+    grid_of_lines(input, |i, line, data| {
+        let mut len = 0;
+        for c in line.chars() {
+            len += 1;
+            let cell = c.encode_utf8(&mut [0; 4]).parse::<T>().map_err(|e| {
+                ParseError::new(i, len, line, &format!("{} ({e})", std::any::type_name::<T>()))
+            })?;
+            data.push(cell);
+        }
+        Ok(len)
+    })
+}
+
+/// Grid of the bytes of `input` mapped by `map`.
+///
+/// Fails where `map` does, its error telling what was expected, or on a line not as long as the first one.
+pub fn input_to_grid_with<T, E, F>(input: &str, mut map: F) -> Result<CustomGrid<T>, ParseError>
+    where E: Display,
+          F: FnMut(u8) -> Result<T, E> {
+    grid_of_lines(input, |i, line, data| {
+        for (j, &byte) in line.as_bytes().iter().enumerate() {
+            data.push(map(byte).map_err(|e| ParseError::new(i, j + 1, line, &e.to_string()))?);
+        }
+        Ok(line.len())
+    })
+}
+
+/// Same as [`input_to_grid_with`], except that lines shorter than the longest one are padded with
+/// `padding`.
+pub fn input_to_grid_padded<T, E, F>(input: &str, padding: T, mut map: F) -> Result<CustomGrid<T>, ParseError>
+    where T: Clone,
+          E: Display,
+          F: FnMut(u8) -> Result<T, E> {
+    let lines: Vec<&str> = input.trim_end_matches(['\n', '\r']).lines().collect();
+    let cols = lines.iter().map(|line| line.len()).max().unwrap_or(0);
+    let mut data = Vec::with_capacity(lines.len() * cols);
+
+    for (i, line) in lines.iter().enumerate() {
+        for (j, &byte) in line.as_bytes().iter().enumerate() {
+            data.push(map(byte).map_err(|e| ParseError::new(i + 1, j + 1, line, &e.to_string()))?);
+        }
+        data.resize(data.len() + cols - line.len(), padding.clone());
+    }
 
-    let grid_data: Result<Vec<T>, <T as FromStr>::Err> = lines
-        .into_iter()
-        .flat_map(|line| line.chars())
-        .map(|c| c.to_string().parse::<T>())
-        .collect();
+    Ok(CustomGrid(Grid::from_vec(data, cols)))
+}
 
-    Ok(CustomGrid(Grid::from_vec(grid_data?, cols)))
-}
\ No newline at end of file
+#[cfg(test)]
+mod tests {
+    use super::{AocParse, input_to_grid, input_to_grid_padded, input_to_grid_with, input_to_list, input_to_records, normalize_input,
+                ParseError, sections};
+
+    #[derive(AocParse, Debug, PartialEq)]
+    #[aoc(format = "{name} -> {signal}")]
+    struct Wire {
+        name: String,
+        signal: u16,
+    }
+
+    #[test]
+    fn library_parser_errors() {
+        let error = input_to_list::<u32>("12\n  3a\n4").unwrap_err();
+        assert_eq!((error.line, error.column, error.content.as_str()), (2, 3, "  3a"));
+        assert!(error.expected.starts_with("u32"));
+
+        let error = input_to_grid::<u8>("123\n4x6").unwrap_err();
+        assert_eq!((error.line, error.column, error.content.as_str()), (2, 2, "4x6"));
+
+        let grid = input_to_grid::<char>("█·\n ·\n").unwrap();
+        assert_eq!((grid.size(), grid[(0, 0)], grid[(1, 0)]), ((2, 2), '█', ' '));
+    }
+
+    #[test]
+    fn derived_line_errors() {
+        let wires = input_to_records::<Wire>("a -> 1\nb -> 2\n").unwrap();
+        assert_eq!(wires[1], Wire { name: "b".to_string(), signal: 2 });
+
+        let input = "a -> 1\nb -> 2\n  c -> x\nd -> 4";
+        let error = input_to_records::<Wire>(input).unwrap_err();
+        assert_eq!((error.line, error.column, error.content.as_str()), (3, 8, "  c -> x"));
+        assert!(error.expected.starts_with("u16 for `signal`"));
+
+        // Only the line is known to `input_to_list`
+        let error = input_to_list::<Wire>(input).unwrap_err();
+        assert_eq!((error.line, error.column), (3, 3));
+    }
+
+    #[test]
+    fn grid_parsers() {
+        let digit = |byte: u8| if byte.is_ascii_digit() { Ok(byte - b'0') } else { Err("a digit") };
+
+        let grid = input_to_grid_with("12\n34\n\n", digit).unwrap();
+        assert_eq!(format!("{grid:?}"), "12\n34\n");
+        assert_eq!(input_to_grid_with("", digit).unwrap().size(), (0, 0));
+
+        let error = input_to_grid_with("12\n3x", digit).unwrap_err();
+        assert_eq!(error, ParseError::new(2, 2, "3x", "a digit"));
+
+        let error = input_to_grid_with("123\n45\n678", digit).unwrap_err();
+        assert_eq!(error, ParseError::new(2, 3, "45", "3 cells like the first line, found 2"));
+
+        // Spaces are cells, on either side of a line
+        let error = input_to_grid_with(" 12\n34 \n56", digit).unwrap_err();
+        assert_eq!((error.line, error.column), (1, 1));
+
+        let grid = input_to_grid_padded("  #\n##  \n#", ' ', |byte| Ok::<char, String>(byte as char)).unwrap();
+        assert_eq!(format!("{grid:?}"), "  # \n##  \n#   \n");
+    }
+
+    #[test]
+    fn input_sections() {
+        let input = "\r\nseeds: 1 2\r\n\r\nmap:\r\n1 2 3\r\n4 5 6\r\n  \r\n\r\nlast\n";
+        assert_eq!(sections(input).collect::<Vec<_>>(), vec!["seeds: 1 2", "map:\r\n1 2 3\r\n4 5 6", "last"]);
+        assert_eq!(sections("a\n\nb").collect::<Vec<_>>(), vec!["a", "b"]);
+        assert_eq!(sections(" \n\n").count(), 0);
+
+        assert_eq!(normalize_input(input), "\nseeds: 1 2\n\nmap:\n1 2 3\n4 5 6\n\n\nlast\n");
+        assert_eq!(normalize_input("#. \n.#\n\n\n"), "#. \n.#\n");
+        assert_eq!(normalize_input(""), "");
+    }
+}
@@ -0,0 +1,224 @@
+use std::error::Error;
+use std::str::FromStr;
+
+use crate::parse::ParseError;
+
+/// Reads a line piece by piece, for the `FromStr` implementations generated by `#[derive(AocParse)]`.
+///
+/// Each field is read up to the literal text following it in the format, or up to the end of the line,
+/// then trimmed and parsed with its own `FromStr`.
+pub struct LineParser<'a> {
+    input: &'a str,
+    position: usize,
+}
+
+impl<'a> LineParser<'a> {
+    pub fn new(input: &'a str) -> Self {
+        Self {
+            input: input.trim_end_matches(['\r', '\n']),
+            position: 0,
+        }
+    }
+
+    fn column(&self, position: usize) -> usize {
+        self.input[..position].chars().count() + 1
+    }
+
+    fn error(&self, position: usize, expected: &str) -> ParseError {
+        ParseError::new(1, self.column(position), self.input, expected)
+    }
+
+    pub fn literal(&mut self, literal: &str) -> Result<(), ParseError> {
+        if self.input[self.position..].starts_with(literal) {
+            self.position += literal.len();
+            Ok(())
+        } else {
+            Err(self.error(self.position, &format!("`{literal}`")))
+        }
+    }
+
+    // Raw text of the next field and where it starts, consuming the literal after it
+    fn take(&mut self, until: Option<&str>) -> Result<(usize, &'a str), ParseError> {
+        let start = self.position;
+        let rest = &self.input[start..];
+        let length = match until {
+            None => rest.len(),
+            Some(literal) => rest
+                .find(literal)
+                .ok_or_else(|| self.error(self.input.len(), &format!("`{literal}`")))?,
+        };
+
+        self.position = start + length + until.map_or(0, str::len);
+        Ok((start, &rest[..length]))
+    }
+
+    fn parse_value<T>(&self, name: &str, start: usize, raw: &str) -> Result<T, ParseError>
+        where T: FromStr,
+              T::Err: Error + 'static {
+        let value = raw.trim();
+        let value_start = start + (raw.len() - raw.trim_start().len());
+
+        value.parse().map_err(|e: T::Err| {
+            match (&e as &dyn Error).downcast_ref::<ParseError>() {
+                // Error inside a nested format: point at it in the whole line
+                Some(nested) => nested.clone().relocate(1, self.column(value_start) - 1, self.input),
+                None => self.error(value_start, &format!("{} for `{name}` ({e})", std::any::type_name::<T>())),
+            }
+        })
+    }
+
+    pub fn field<T>(&mut self, name: &str, until: Option<&str>) -> Result<T, ParseError>
+        where T: FromStr,
+              T::Err: Error + 'static {
+        let (start, raw) = self.take(until)?;
+        self.parse_value(name, start, raw)
+    }
+
+    /// `None` when the field is blank.
+    pub fn optional<T>(&mut self, name: &str, until: Option<&str>) -> Result<Option<T>, ParseError>
+        where T: FromStr,
+              T::Err: Error + 'static {
+        let (start, raw) = self.take(until)?;
+        if raw.trim().is_empty() {
+            Ok(None)
+        } else {
+            self.parse_value(name, start, raw).map(Some)
+        }
+    }
+
+    /// Items separated by `separator`. Empty items are skipped when the separator is blank, so that
+    /// numbers aligned with several spaces can be read.
+    pub fn list<T>(&mut self, name: &str, separator: &str, until: Option<&str>) -> Result<Vec<T>, ParseError>
+        where T: FromStr,
+              T::Err: Error + 'static {
+        let (start, raw) = self.take(until)?;
+        if raw.trim().is_empty() {
+            return Ok(vec![]);
+        }
+
+        let blank_separator = separator.trim().is_empty();
+        let mut offset = start;
+        let mut items = vec![];
+        for item in raw.split(separator) {
+            if !(blank_separator && item.trim().is_empty()) {
+                items.push(self.parse_value(name, offset, item)?);
+            }
+            offset += item.len() + separator.len();
+        }
+
+        Ok(items)
+    }
+
+    /// Checks that the whole line was read.
+    pub fn end(&self) -> Result<(), ParseError> {
+        if self.position >= self.input.len() {
+            Ok(())
+        } else {
+            Err(self.error(self.position, "end of line"))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{AocParse, ParseError};
+
+    #[derive(AocParse, Debug, PartialEq)]
+    enum Color {
+        Red,
+        Green,
+        #[aoc(keyword = "b")]
+        Blue,
+    }
+
+    #[derive(AocParse, Debug, PartialEq)]
+    #[aoc(format = "{0} {1}")]
+    struct Cubes(u32, Color);
+
+    #[derive(AocParse, Debug, PartialEq)]
+    #[aoc(format = "Game {id}: {draws:sep(';')}")]
+    struct Game {
+        id: u32,
+        draws: Vec<Draw>,
+    }
+
+    #[derive(AocParse, Debug, PartialEq)]
+    #[aoc(format = "{cubes:sep(',')}")]
+    struct Draw {
+        cubes: Vec<Cubes>,
+    }
+
+    #[derive(AocParse, Debug, PartialEq)]
+    enum Instruction {
+        #[aoc(format = "turn {on} {from} through {to}")]
+        Turn {
+            on: String,
+            from: Point,
+            to: Point,
+        },
+        #[aoc(format = "toggle {0} through {1}")]
+        Toggle(Point, Point),
+        Noop,
+    }
+
+    #[derive(AocParse, Debug, PartialEq)]
+    #[aoc(format = "{x},{y}")]
+    struct Point {
+        x: i32,
+        y: i32,
+    }
+
+    #[derive(AocParse, Debug, PartialEq)]
+    #[aoc(format = "{name} [{weight}] {{{children:sep(' ')}}}")]
+    struct Node {
+        name: String,
+        weight: Option<u32>,
+        children: Vec<String>,
+    }
+
+    #[test]
+    fn nested_lists_and_keywords() {
+        let game: Game = "Game 3: 8 green, 6 b; 20 red".parse().unwrap();
+
+        assert_eq!(game, Game {
+            id: 3,
+            draws: vec![
+                Draw { cubes: vec![Cubes(8, Color::Green), Cubes(6, Color::Blue)] },
+                Draw { cubes: vec![Cubes(20, Color::Red)] },
+            ],
+        });
+    }
+
+    #[test]
+    fn enum_variants() {
+        assert_eq!("toggle 0,0 through 9,-1".parse(), Ok(Instruction::Toggle(Point { x: 0, y: 0 }, Point { x: 9, y: -1 })));
+        assert_eq!("turn off 1,2 through 3,4".parse(), Ok(Instruction::Turn {
+            on: "off".to_string(),
+            from: Point { x: 1, y: 2 },
+            to: Point { x: 3, y: 4 },
+        }));
+        assert_eq!(" noop ".parse(), Ok(Instruction::Noop));
+    }
+
+    #[test]
+    fn optional_fields() {
+        assert_eq!("a [] {b  c}".parse(), Ok(Node { name: "a".to_string(), weight: None, children: vec!["b".to_string(), "c".to_string()] }));
+        assert_eq!("a [3] {}".parse(), Ok(Node { name: "a".to_string(), weight: Some(3), children: vec![] }));
+    }
+
+    #[test]
+    fn errors() {
+        let error = "Game 3: 8 green, 6 purple".parse::<Game>().unwrap_err();
+        assert_eq!(error, ParseError::new(1, 20, "Game 3: 8 green, 6 purple", "one of `red`, `green`, `b`"));
+
+        let error = "Game x: 1 red".parse::<Game>().unwrap_err();
+        assert_eq!((error.column, error.expected.starts_with("u32 for `id`")), (6, true));
+
+        assert_eq!("Gam 1: 1 red".parse::<Game>().unwrap_err().expected, "`Game `");
+        assert_eq!("Game 1 1 red".parse::<Game>().unwrap_err().expected, "`: `");
+
+        let error = "toggle 0,0 through 9,x".parse::<Instruction>().unwrap_err();
+        assert_eq!(error.column, 22);
+        assert_eq!("jump".parse::<Instruction>().unwrap_err().expected, "one of `turn {on} {from} through {to}`, `toggle {0} through {1}`, `noop`");
+    }
+}
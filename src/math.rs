@@ -0,0 +1,304 @@
+pub fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
+
+pub fn lcm(a: u64, b: u64) -> u64 {
+    if a == 0 || b == 0 { 0 } else { a / gcd(a, b) * b }
+}
+
+/// Greatest common divisor of all the values, 0 if there are none.
+pub fn gcd_all<I: IntoIterator<Item=u64>>(values: I) -> u64 {
+    values.into_iter().fold(0, gcd)
+}
+
+/// Least common multiple of all the values, 1 if there are none.
+pub fn lcm_all<I: IntoIterator<Item=u64>>(values: I) -> u64 {
+    values.into_iter().fold(1, lcm)
+}
+
+/// `(g, x, y)` such that `a * x + b * y = g`, `g` being the non-negative gcd of `a` and `b`.
+pub fn extended_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+
+    while r != 0 {
+        let quotient = old_r / r;
+        (old_r, r) = (r, old_r - quotient * r);
+        (old_x, x) = (x, old_x - quotient * x);
+        (old_y, y) = (y, old_y - quotient * y);
+    }
+
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// `x` in `0..modulus` such that `a * x ≡ 1 (mod modulus)`, if `a` and `modulus` are coprime.
+pub fn mod_inverse(a: i64, modulus: i64) -> Option<i64> {
+    let (g, x, _) = extended_gcd(a.rem_euclid(modulus), modulus);
+    (g == 1).then(|| x.rem_euclid(modulus))
+}
+
+/// `base ^ exp % modulus`, by squaring.
+pub fn mod_pow(base: u64, mut exp: u64, modulus: u64) -> u64 {
+    if modulus == 1 {
+        return 0;
+    }
+
+    let modulus = modulus as u128;
+    let mut base = base as u128 % modulus;
+    let mut result = 1;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base % modulus;
+        }
+        base = base * base % modulus;
+        exp >>= 1;
+    }
+
+    result as u64
+}
+
+/// Chinese Remainder Theorem for `x ≡ residue (mod modulus)` congruences, moduli not having to be coprime.
+///
+/// Returns `(x, lcm of the moduli)` with `x` the smallest non-negative solution, `None` if the congruences
+/// contradict each other.
+pub fn crt(congruences: &[(i64, i64)]) -> Option<(i64, i64)> {
+    congruences.iter().try_fold((0, 1), |(x, modulus), &(residue, other_modulus)| {
+        let (g, p, _) = extended_gcd(modulus, other_modulus);
+        let difference = residue - x;
+        if difference % g != 0 {
+            return None;
+        }
+
+        // x + modulus * k ≡ residue (mod other_modulus), k = difference / g * p (mod other_modulus / g)
+        let step = other_modulus / g;
+        let k = (difference / g) as i128 * p as i128 % step as i128;
+        let lcm = modulus as i128 * step as i128;
+        let x = (x as i128 + modulus as i128 * k).rem_euclid(lcm);
+
+        Some((i64::try_from(x).ok()?, i64::try_from(lcm).ok()?))
+    })
+}
+
+/// First time at which all the cycles are aligned, each `(offset, period)` cycle being aligned at
+/// `offset`, `offset + period`, `offset + 2 * period`...
+///
+/// With every offset at 0 this is the lcm of the periods.
+pub fn align_cycles(cycles: &[(u64, u64)]) -> Option<u64> {
+    let congruences: Vec<(i64, i64)> = cycles
+        .iter()
+        .map(|&(offset, period)| (offset as i64, period as i64))
+        .collect();
+    let (x, modulus) = crt(&congruences)?;
+
+    // The smallest solution can come before some cycles start
+    let start = cycles.iter().map(|&(offset, _)| offset).max().unwrap_or(0);
+    let (x, modulus) = (x as u64, modulus as u64);
+    if x >= start {
+        Some(x)
+    } else {
+        Some(x + (start - x).div_ceil(modulus) * modulus)
+    }
+}
+
+/// Floor of the square root of `n`.
+pub fn isqrt_u64(n: u64) -> u64 {
+    isqrt_u128(n as u128) as u64
+}
+
+/// Floor of the square root of `n`, exact where an `f64` square root loses precision.
+pub fn isqrt_u128(n: u128) -> u128 {
+    if n < 2 {
+        return n;
+    }
+
+    // Newton's method from above converges down to the floor
+    let mut x = 1u128 << (n.ilog2() / 2 + 1);
+    loop {
+        let next = (x + n / x) / 2;
+        if next >= x {
+            return x;
+        }
+        x = next;
+    }
+}
+
+/// First and last integers strictly between the roots of `a * x² + b * x + c` (`a > 0`), i.e. where
+/// it's negative, `None` if there are none.
+pub fn between_roots(a: i64, b: i64, c: i64) -> Option<(i64, i64)> {
+    assert!(a > 0, "the quadratic must open upwards");
+    let (a, b, c) = (a as i128, b as i128, c as i128);
+    let value = |x: i128| a * x * x + b * x + c;
+
+    let discriminant = b * b - 4 * a * c;
+    if discriminant <= 0 {
+        return None;
+    }
+
+    // Integer approximations of the roots, off by at most one, then fixed by checking the sign
+    let root = isqrt_u128(discriminant as u128) as i128;
+    let mut first = (-b - root).div_euclid(2 * a) - 1;
+    let mut last = (-b + root + 2 * a - 1).div_euclid(2 * a) + 1;
+    while first <= last && value(first) >= 0 {
+        first += 1;
+    }
+    while last >= first && value(last) >= 0 {
+        last -= 1;
+    }
+
+    (first <= last).then_some((first as i64, last as i64))
+}
+
+/// Number of integers strictly between the roots of `a * x² + b * x + c` (`a > 0`).
+pub fn count_between_roots(a: i64, b: i64, c: i64) -> u64 {
+    between_roots(a, b, c).map_or(0, |(first, last)| first.abs_diff(last) + 1)
+}
+
+/// Integer solutions of `a * x + b * y = c`, with `a` or `b` not 0.
+///
+/// Returns `(x, y, dx, dy)`: the solutions are `(x + k * dx, y - k * dy)` for any integer `k`.
+pub fn solve_linear_diophantine(a: i64, b: i64, c: i64) -> Option<(i64, i64, i64, i64)> {
+    let (g, x, y) = extended_gcd(a, b);
+    if g == 0 || c % g != 0 {
+        return None;
+    }
+
+    let factor = c / g;
+    Some((x * factor, y * factor, b / g, a / g))
+}
+
+/// Unique integer solution `(x, y)` of the system `a * x + b * y = e` and `c * x + d * y = f`.
+///
+/// `None` if the system has no solution, infinitely many, or only a non-integer one.
+pub fn solve_linear_system(a: i64, b: i64, c: i64, d: i64, e: i64, f: i64) -> Option<(i64, i64)> {
+    // Cramer's rule
+    let determinant = a * d - b * c;
+    if determinant == 0 {
+        return None;
+    }
+
+    let x = e * d - b * f;
+    let y = a * f - e * c;
+    (x % determinant == 0 && y % determinant == 0).then(|| (x / determinant, y / determinant))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{align_cycles, between_roots, count_between_roots, crt, extended_gcd, gcd_all, isqrt_u128, isqrt_u64,
+                lcm_all, mod_inverse, mod_pow, solve_linear_diophantine, solve_linear_system};
+
+    #[test]
+    fn gcd_lcm() {
+        assert_eq!(gcd_all([12, 18, 30]), 6);
+        assert_eq!(gcd_all([]), 0);
+        assert_eq!(lcm_all([4, 6, 10]), 60);
+        assert_eq!(lcm_all([]), 1);
+    }
+
+    #[test]
+    fn extended_euclid_and_inverse() {
+        for a in -30..30 {
+            for b in -30..30 {
+                let (g, x, y) = extended_gcd(a, b);
+                assert_eq!(a * x + b * y, g);
+                assert_eq!(g as u64, num::integer::gcd(a, b) as u64);
+            }
+        }
+
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(-3, 11), Some(7));
+        assert_eq!(mod_inverse(6, 9), None);
+    }
+
+    #[test]
+    fn modular_exponent() {
+        assert_eq!(mod_pow(2, 10, 1000), 24);
+        assert_eq!(mod_pow(7, 0, 13), 1);
+        assert_eq!(mod_pow(5, 3, 1), 0);
+        // Large enough to overflow u64 multiplications
+        assert_eq!(mod_pow(u64::MAX - 1, 2, u64::MAX), 1);
+    }
+
+    #[test]
+    fn chinese_remainder() {
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        // Non coprime moduli
+        assert_eq!(crt(&[(1, 4), (3, 6)]), Some((9, 12)));
+        assert_eq!(crt(&[(0, 4), (1, 6)]), None);
+
+        for a in 0..6 {
+            for b in 0..10 {
+                let brute_force = (0..30).find(|x| x % 6 == a && x % 10 == b);
+                assert_eq!(crt(&[(a, 6), (b, 10)]).map(|(x, _)| x), brute_force);
+            }
+        }
+    }
+
+    #[test]
+    fn aligned_cycles() {
+        assert_eq!(align_cycles(&[(2, 2), (3, 3)]), Some(6));
+        assert_eq!(align_cycles(&[(3, 4), (5, 6)]), Some(11));
+        // Solution 1 modulo 6 comes before the second cycle starts
+        assert_eq!(align_cycles(&[(1, 2), (4, 3)]), Some(7));
+        assert_eq!(align_cycles(&[(0, 2), (1, 4)]), None);
+    }
+
+    #[test]
+    fn integer_square_root() {
+        for n in 0..10_000u64 {
+            let root = isqrt_u64(n);
+            assert!(root * root <= n && (root + 1) * (root + 1) > n, "isqrt({n}) = {root}");
+        }
+
+        assert_eq!(isqrt_u64(u64::MAX), u32::MAX as u64);
+        assert_eq!(isqrt_u128(u128::MAX), u64::MAX as u128);
+        let big = (1u128 << 60) + 12345;
+        assert_eq!(isqrt_u128(big * big), big);
+        assert_eq!(isqrt_u128(big * big - 1), big - 1);
+    }
+
+    #[test]
+    fn quadratic_roots() {
+        // Hold times beating the record of the 2023-06 example races: x * (time - x) > distance
+        assert_eq!(count_between_roots(1, -7, 9), 4);
+        assert_eq!(count_between_roots(1, -30, 200), 9);
+        assert_eq!(count_between_roots(1, -71530, 940200), 71503);
+
+        for a in 1..4 {
+            for b in -15..15 {
+                for c in -15..15 {
+                    let negative: Vec<i64> = (-30..30).filter(|x| a * x * x + b * x + c < 0).collect();
+                    let expected = negative.first().map(|&first| (first, *negative.last().unwrap()));
+                    assert_eq!(between_roots(a, b, c), expected, "{a}x² + {b}x + {c}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn linear_equations() {
+        for a in -6..6 {
+            for b in -6..6 {
+                for c in -10..10 {
+                    let brute_force = (-20..20).any(|x| (-20..20).any(|y| a * x + b * y == c));
+                    match solve_linear_diophantine(a, b, c) {
+                        Some((x, y, dx, dy)) => {
+                            for k in -2..2 {
+                                assert_eq!(a * (x + k * dx) + b * (y - k * dy), c);
+                            }
+                        }
+                        None => assert!(!brute_force || (a == 0 && b == 0), "{a}x + {b}y = {c}"),
+                    }
+                }
+            }
+        }
+
+        assert_eq!(solve_linear_system(94, 22, 34, 67, 8400, 5400), Some((80, 40)));
+        assert_eq!(solve_linear_system(26, 67, 66, 21, 12748, 12176), None);
+        assert_eq!(solve_linear_system(1, 2, 2, 4, 3, 6), None);
+    }
+}
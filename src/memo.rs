@@ -0,0 +1,158 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Cache of the values of a function, living as long as the `Memo` does, so that its keys can
+/// borrow from the input and nothing is kept from one call of a solution to the next.
+///
+/// [`Memo::solve`] takes a recursive function which, instead of calling itself, calls the
+/// `recurse` closure it is given, cached values being returned without calling it again:
+///
+/// ```ignore
+/// let mut memo = Memo::new();
+/// let ways = memo.solve(target, &|recurse, n: u64| match n {
+///     0 => 1,
+///     n => (1..=n.min(3)).map(|step| recurse(n - step)).sum::<u64>(),
+/// });
+/// ```
+pub struct Memo<K, V> {
+    cache: HashMap<K, V>,
+    hits: u64,
+    misses: u64,
+}
+
+/// How well a [`Memo`] did.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MemoStats {
+    pub entries: usize,
+    pub hits: u64,
+    pub misses: u64,
+}
+
+impl MemoStats {
+    /// Share of the lookups answered from the cache.
+    pub fn hit_rate(&self) -> f64 {
+        match self.hits + self.misses {
+            0 => 0.0,
+            lookups => self.hits as f64 / lookups as f64,
+        }
+    }
+}
+
+impl<K: Hash + Eq + Clone, V: Clone> Memo<K, V> {
+    pub fn new() -> Self {
+        Self {
+            cache: HashMap::new(),
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    /// Value of `function` for `key`, `function` being given a closure to get its values for other keys.
+    pub fn solve<F>(&mut self, key: K, function: &F) -> V
+        where F: Fn(&mut dyn FnMut(K) -> V, K) -> V {
+        if let Some(value) = self.cache.get(&key) {
+            self.hits += 1;
+            return value.clone();
+        }
+
+        self.misses += 1;
+        let value = function(&mut |key| self.solve(key, function), key.clone());
+        self.cache.insert(key, value.clone());
+        value
+    }
+
+    /// Cached value for `key`, or the one given by `compute` which gets cached.
+    pub fn get_or_insert_with(&mut self, key: K, compute: impl FnOnce() -> V) -> V {
+        if let Some(value) = self.cache.get(&key) {
+            self.hits += 1;
+            return value.clone();
+        }
+
+        self.misses += 1;
+        let value = compute();
+        self.cache.insert(key, value.clone());
+        value
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.cache.get(key)
+    }
+
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    pub fn stats(&self) -> MemoStats {
+        MemoStats {
+            entries: self.cache.len(),
+            hits: self.hits,
+            misses: self.misses,
+        }
+    }
+
+    /// Empties the cache and resets the stats.
+    pub fn clear(&mut self) {
+        self.cache.clear();
+        self.hits = 0;
+        self.misses = 0;
+    }
+}
+
+impl<K: Hash + Eq + Clone, V: Clone> Default for Memo<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Memo, MemoStats};
+
+    fn fibonacci(recurse: &mut dyn FnMut(u64) -> u64, n: u64) -> u64 {
+        match n {
+            0 | 1 => n,
+            n => recurse(n - 1) + recurse(n - 2),
+        }
+    }
+
+    #[test]
+    fn recursion_and_stats() {
+        let mut memo = Memo::new();
+        assert_eq!(memo.solve(90, &fibonacci), 2880067194370816120);
+
+        // Each value is computed once, then found for the second term of the next one up
+        assert_eq!(memo.stats(), MemoStats { entries: 91, hits: 88, misses: 91 });
+        assert_eq!(memo.solve(50, &fibonacci), 12586269025);
+        assert_eq!(memo.stats().hits, 89);
+        assert!((memo.stats().hit_rate() - 89.0 / 180.0).abs() < 1e-9);
+
+        memo.clear();
+        assert_eq!((memo.is_empty(), memo.stats().hit_rate()), (true, 0.0));
+    }
+
+    #[test]
+    fn borrowed_keys() {
+        // Ways to build the text from the words, keyed by the remaining text
+        let input = String::from("ab, b, a, ba | abab");
+        let (words, text) = input.split_once(" | ").unwrap();
+        let words: Vec<&str> = words.split(", ").collect();
+
+        let mut memo: Memo<&str, u64> = Memo::new();
+        let ways = memo.solve(text, &|recurse, rest: &str| match rest {
+            "" => 1,
+            rest => words
+                .iter()
+                .filter_map(|word| rest.strip_prefix(word))
+                .map(recurse)
+                .sum(),
+        });
+
+        assert_eq!(ways, 5);
+        assert_eq!(memo.get(&"ab"), Some(&2));
+        assert_eq!(memo.get_or_insert_with("bab", || unreachable!()), 3);
+    }
+}
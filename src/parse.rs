@@ -0,0 +1,268 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use nom::{
+    IResult,
+    error::ErrorKind,
+    character::complete::{char, digit1, line_ending, one_of, space0, space1},
+    combinator::{all_consuming, map_res, opt, recognize},
+    multi::separated_list1,
+    sequence::{pair, preceded, separated_pair, terminated, tuple},
+};
+
+/// Where and why an input couldn't be parsed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    /// Starting at 1
+    pub line: usize,
+    /// Starting at 1, in characters
+    pub column: usize,
+    /// The offending line
+    pub content: String,
+    pub expected: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, content: &str, expected: &str) -> Self {
+        Self {
+            line,
+            column,
+            content: content.to_string(),
+            expected: expected.to_string(),
+        }
+    }
+
+    /// Error at the start of `rest`, the part of `input` that couldn't be parsed.
+    pub fn at(input: &str, rest: &str, expected: &str) -> Self {
+        let offset = input.len() - rest.len();
+        let before = &input[..offset];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        let content = input[line_start..].lines().next().unwrap_or("");
+
+        Self::new(
+            before.matches('\n').count() + 1,
+            before[line_start..].chars().count() + 1,
+            content,
+            expected,
+        )
+    }
+
+    /// Same error on line `line`, for an error of a piece of `content` starting after
+    /// `column_offset` characters, which was parsed on its own.
+    pub fn relocate(self, line: usize, column_offset: usize, content: &str) -> Self {
+        Self {
+            line,
+            column: column_offset + self.column,
+            content: content.to_string(),
+            ..self
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let margin = " ".repeat(self.line.to_string().len());
+        writeln!(f, "Parse error at line {}, column {}: expected {}", self.line, self.column, self.expected)?;
+        writeln!(f, "{} | {}", self.line, self.content.trim_end())?;
+        write!(f, "{margin} | {}^", " ".repeat(self.column.saturating_sub(1)))
+    }
+}
+
+impl Error for ParseError {}
+
+/// Number without sign, e.g. `42`.
+pub fn unsigned<T: FromStr>(input: &str) -> IResult<&str, T> {
+    map_res(digit1, |s: &str| s.parse::<T>())(input)
+}
+
+/// Number with an optional `-` or `+` sign, e.g. `-42`.
+pub fn signed<T: FromStr>(input: &str) -> IResult<&str, T> {
+    map_res(recognize(pair(opt(one_of("+-")), digit1)), |s: &str| s.parse::<T>())(input)
+}
+
+/// Items separated by commas, with optional spaces around them, e.g. `1, 2,3`.
+pub fn comma_list<'a, O, F>(item: F) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>>
+    where F: FnMut(&'a str) -> IResult<&'a str, O> {
+    separated_list1(tuple((space0, char(','), space0)), item)
+}
+
+/// Items separated by one or more spaces, leading spaces skipped, e.g. ` 1  2 3`.
+///
+/// Trailing spaces are left unparsed.
+pub fn space_list<'a, O, F>(item: F) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>>
+    where F: FnMut(&'a str) -> IResult<&'a str, O> {
+    preceded(space0, separated_list1(space1, item))
+}
+
+/// Items one per line, e.g. a whole input or the body of a section.
+pub fn lines<'a, O, F>(item: F) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>>
+    where F: FnMut(&'a str) -> IResult<&'a str, O> {
+    separated_list1(line_ending, item)
+}
+
+/// `key: value`, with optional spaces around the colon, e.g. `Card 1: 41 48 83`.
+pub fn key_value<'a, K, V, FK, FV>(key: FK, value: FV) -> impl FnMut(&'a str) -> IResult<&'a str, (K, V)>
+    where FK: FnMut(&'a str) -> IResult<&'a str, K>,
+          FV: FnMut(&'a str) -> IResult<&'a str, V> {
+    separated_pair(key, tuple((space0, char(':'), space0)), value)
+}
+
+/// `x,y` signed coordinates, with optional spaces after the comma, e.g. `0,-9`.
+pub fn coordinates<T: FromStr>(input: &str) -> IResult<&str, (T, T)> {
+    separated_pair(signed, pair(char(','), space0), signed)(input)
+}
+
+/// Sections separated by a blank line, e.g. the seeds and each map of an almanac.
+///
+/// Unlike [`crate::sections`], which splits the input before it is parsed, it parses each section.
+pub fn section_list<'a, O, F>(section: F) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>>
+    where F: FnMut(&'a str) -> IResult<&'a str, O> {
+    separated_list1(pair(line_ending, line_ending), section)
+}
+
+/// Line with a label ending with a colon, followed by a body on the next lines, e.g.
+/// `seed-to-soil map:` followed by the map entries.
+pub fn labeled_block<'a, L, B, FL, FB>(label: FL, body: FB) -> impl FnMut(&'a str) -> IResult<&'a str, (L, B)>
+    where FL: FnMut(&'a str) -> IResult<&'a str, L>,
+          FB: FnMut(&'a str) -> IResult<&'a str, B> {
+    separated_pair(label, tuple((char(':'), space0, line_ending)), body)
+}
+
+/// Runs `parser` on the whole `input`, ignoring a final line ending.
+///
+/// The error is where nom gave up: as lists backtrack out of an item they can't parse, it is then
+/// at the end of the list, expecting the end of the input, rather than in that item.
+pub fn try_parse_all<'a, O, F>(parser: F, input: &'a str) -> Result<O, ParseError>
+    where F: FnMut(&'a str) -> IResult<&'a str, O> {
+    match all_consuming(terminated(parser, opt(line_ending)))(input) {
+        Ok((_, output)) => Ok(output),
+        Err(nom::Err::Error(e) | nom::Err::Failure(e)) => {
+            let expected = match e.code {
+                ErrorKind::Eof => "end of input".to_string(),
+                code => code.description().to_lowercase(),
+            };
+            Err(ParseError::at(input, e.input, &expected))
+        }
+        Err(nom::Err::Incomplete(_)) => Err(ParseError::at(input, "", "more input")),
+    }
+}
+
+/// Runs `parser` on the whole `input`, ignoring a final line ending.
+///
+/// # Panics
+///
+/// If `parser` fails or leaves something unparsed, like the parsers of each day.
+pub fn parse_all<'a, O, F>(parser: F, input: &'a str) -> O
+    where F: FnMut(&'a str) -> IResult<&'a str, O> {
+    match try_parse_all(parser, input) {
+        Ok(output) => output,
+        Err(e) => panic!("Unable to parse input\n{e}"),
+    }
+}
+
+/// Value of a parse result, panicking with the error displayed, with its line and a caret under
+/// where it is, rather than with its `Debug` as `unwrap` would.
+#[track_caller]
+pub fn unwrap_parsed<T>(result: Result<T, ParseError>) -> T {
+    result.unwrap_or_else(|e| panic!("Unable to parse input\n{e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use nom::bytes::complete::tag;
+    use nom::character::complete::{alpha1, not_line_ending};
+    use nom::sequence::{preceded, separated_pair};
+
+    use super::{comma_list, coordinates, key_value, labeled_block, lines, parse_all, ParseError, section_list, signed,
+                space_list, try_parse_all, unsigned, unwrap_parsed};
+
+    #[test]
+    fn numbers() {
+        assert_eq!(unsigned::<u32>("42 rest"), Ok((" rest", 42)));
+        assert!(unsigned::<u32>("-42").is_err());
+        assert!(unsigned::<u8>("256").is_err());
+        assert_eq!(signed::<i64>("-42"), Ok(("", -42)));
+        assert_eq!(signed::<i64>("+7,"), Ok((",", 7)));
+        assert!(signed::<i64>("- 1").is_err());
+    }
+
+    #[test]
+    fn separated_lists() {
+        assert_eq!(comma_list(signed::<i32>)("3,-4, 5 ,6 end"), Ok((" end", vec![3, -4, 5, 6])));
+        assert_eq!(space_list(unsigned::<u32>)(" 41 48  6 | 83"), Ok((" | 83", vec![41, 48, 6])));
+        assert_eq!(lines(alpha1)("ab\ncd\r\nef"), Ok(("", vec!["ab", "cd", "ef"])));
+    }
+
+    #[test]
+    fn key_values() {
+        let mut card = key_value(preceded(tag("Card"), space_list(unsigned::<u32>)), not_line_ending);
+        assert_eq!(card("Card   1: 41 48"), Ok(("", (vec![1], "41 48"))));
+
+        let mut setting = key_value(alpha1, signed::<i32>);
+        assert_eq!(setting("speed : -3"), Ok(("", ("speed", -3))));
+    }
+
+    #[test]
+    fn coordinate_pairs() {
+        assert_eq!(coordinates::<i32>("0,-9 -> 5,9"), Ok((" -> 5,9", (0, -9))));
+        assert_eq!(coordinates::<u8>("3, 4"), Ok(("", (3, 4))));
+        assert_eq!(
+            separated_pair(coordinates::<i32>, tag(" -> "), coordinates::<i32>)("0,9 -> 5,9"),
+            Ok(("", ((0, 9), (5, 9))))
+        );
+    }
+
+    #[test]
+    fn blank_line_sections() {
+        let input = "1,2\n3,4\n\n5,6\r\n\r\n7,8\n";
+        let parsed = parse_all(section_list(lines(coordinates::<u8>)), input);
+
+        assert_eq!(parsed, vec![vec![(1, 2), (3, 4)], vec![(5, 6)], vec![(7, 8)]]);
+    }
+
+    #[test]
+    fn labeled_blocks() {
+        let input = "seeds: 79 14\n\nseed-to-soil map:\n50 98 2\n52 50 48\n\nsoil-to-fertilizer map:\n0 15 37";
+        let (seeds, maps) = parse_all(
+            separated_pair(
+                key_value(tag("seeds"), space_list(unsigned::<u64>)),
+                tag("\n\n"),
+                section_list(labeled_block(not_colon, lines(space_list(unsigned::<u64>)))),
+            ),
+            input,
+        );
+
+        assert_eq!(seeds, ("seeds", vec![79, 14]));
+        assert_eq!(maps, vec![
+            ("seed-to-soil map", vec![vec![50, 98, 2], vec![52, 50, 48]]),
+            ("soil-to-fertilizer map", vec![vec![0, 15, 37]]),
+        ]);
+    }
+
+    fn not_colon(input: &str) -> nom::IResult<&str, &str> {
+        nom::bytes::complete::take_till1(|c| c == ':')(input)
+    }
+
+    #[test]
+    #[should_panic(expected = "Unable to parse input")]
+    fn leftover_input() {
+        parse_all(comma_list(unsigned::<u32>), "1,2,x");
+    }
+
+    #[test]
+    #[should_panic(expected = "Parse error at line 2, column 3: expected a letter")]
+    fn unwrapping_displays_the_error() {
+        unwrap_parsed::<u32>(Err(ParseError::new(2, 3, "ab?", "a letter")));
+    }
+
+    #[test]
+    fn error_position() {
+        let error = try_parse_all(coordinates::<i32>, "3,4\n5,6").unwrap_err();
+        assert_eq!(error, ParseError::new(2, 1, "5,6", "end of input"));
+
+        let error = try_parse_all(coordinates::<i32>, "3,x").unwrap_err();
+        assert_eq!(error, ParseError::new(1, 3, "3,x", "digit"));
+        assert_eq!(format!("{error}"), "Parse error at line 1, column 3: expected digit\n1 | 3,x\n  |   ^");
+    }
+}
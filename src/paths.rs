@@ -0,0 +1,299 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::graph::{Graph, NodeId};
+
+/// Which nodes a path may go through again.
+///
+/// Limited nodes can be visited at most once, except for `revisits` of them that may be visited twice.
+/// Unlimited nodes can be visited any number of times: two adjacent unlimited nodes make an infinity
+/// of paths if the end can be reached from them.
+#[derive(Clone, Debug)]
+pub struct VisitPolicy {
+    // Bit of each limited node in the visited mask
+    bits: Vec<Option<u32>>,
+    protected: Vec<bool>,
+    revisits: u8,
+}
+
+impl VisitPolicy {
+    /// Every node can be visited any number of times.
+    pub fn unlimited(graph: &Graph) -> Self {
+        Self::at_most_once(graph, |_| false)
+    }
+
+    /// Nodes whose name matches `limited` can be visited at most once, e.g. small caves.
+    ///
+    /// # Panics
+    ///
+    /// If more than 64 nodes are limited.
+    pub fn at_most_once<P: Fn(&str) -> bool>(graph: &Graph, limited: P) -> Self {
+        let mut next_bit = 0;
+        let bits = graph.nodes()
+            .map(|node| {
+                if limited(graph.name(node)) {
+                    assert!(next_bit < u64::BITS, "too many limited nodes for the visited mask");
+                    next_bit += 1;
+                    Some(next_bit - 1)
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        Self {
+            bits,
+            protected: vec![false; graph.len()],
+            revisits: 0,
+        }
+    }
+
+    /// Lets one limited node per path be visited twice.
+    pub fn allow_one_revisit(self) -> Self {
+        self.allow_revisits(1)
+    }
+
+    /// Lets `revisits` different limited nodes per path be visited twice, none of them three times.
+    pub fn allow_revisits(self, revisits: u8) -> Self {
+        Self {
+            revisits,
+            ..self
+        }
+    }
+
+    /// Prevents `node` from being one of the nodes visited twice, e.g. the start.
+    pub fn protect(mut self, node: NodeId) -> Self {
+        self.protected[node] = true;
+        self
+    }
+
+    fn start(&self, node: NodeId) -> (u64, u64) {
+        (self.bits[node].map_or(0, |bit| 1 << bit), 0)
+    }
+
+    // Visited and revisited masks after entering `node`, `None` if it can't be entered
+    fn enter(&self, node: NodeId, mask: u64, revisited: u64) -> Option<(u64, u64)> {
+        match self.bits[node] {
+            None => Some((mask, revisited)),
+            Some(bit) if mask & (1 << bit) == 0 => Some((mask | (1 << bit), revisited)),
+            Some(bit) if revisited & (1 << bit) == 0
+                && revisited.count_ones() < self.revisits as u32
+                && !self.protected[node] => Some((mask, revisited | (1 << bit))),
+            Some(_) => None,
+        }
+    }
+}
+
+/// Number of paths from `start` to `end` allowed by `policy`, without building them.
+///
+/// A path stops as soon as it reaches `end`.
+///
+/// # Panics
+///
+/// If there are infinitely many paths, because of a cycle through unlimited nodes from which `end`
+/// can be reached.
+pub fn count_paths(graph: &Graph, start: NodeId, end: NodeId, policy: &VisitPolicy) -> u64 {
+    let (mask, revisited) = policy.start(start);
+    let mut cache = HashMap::new();
+    let mut in_progress = HashSet::new();
+    let mut cyclic = HashSet::new();
+
+    count_from(graph, end, policy, (start, mask, revisited), &mut cache, &mut in_progress, &mut cyclic)
+}
+
+fn count_from(
+    graph: &Graph,
+    end: NodeId,
+    policy: &VisitPolicy,
+    state: (NodeId, u64, u64),
+    cache: &mut HashMap<(NodeId, u64, u64), u64>,
+    in_progress: &mut HashSet<(NodeId, u64, u64)>,
+    cyclic: &mut HashSet<(NodeId, u64, u64)>,
+) -> u64 {
+    let (node, mask, revisited) = state;
+    if node == end {
+        return 1;
+    }
+    if let Some(&count) = cache.get(&state) {
+        return count;
+    }
+    // Going around a cycle only adds paths if the end can be reached from it, which is checked below
+    if !in_progress.insert(state) {
+        cyclic.insert(state);
+        return 0;
+    }
+
+    let count = graph
+        .neighbors(node)
+        .filter_map(|neighbor| {
+            policy
+                .enter(neighbor, mask, revisited)
+                .map(|(mask, revisited)| (neighbor, mask, revisited))
+        })
+        .map(|next| count_from(graph, end, policy, next, cache, in_progress, cyclic))
+        .sum();
+
+    assert!(count == 0 || !cyclic.contains(&state), "infinitely many paths: cycle through unlimited nodes");
+    in_progress.remove(&state);
+    cache.insert(state, count);
+    count
+}
+
+/// Lazily yields the paths from `start` to `end` allowed by `policy`, see [`count_paths`].
+pub fn paths<'a>(graph: &'a Graph, start: NodeId, end: NodeId, policy: &'a VisitPolicy) -> Paths<'a> {
+    let (mask, revisited) = policy.start(start);
+
+    Paths {
+        graph,
+        policy,
+        end,
+        trivial: start == end,
+        stack: if start == end { vec![] } else { vec![(start, mask, revisited, 0)] },
+        path: vec![start],
+    }
+}
+
+pub struct Paths<'a> {
+    graph: &'a Graph,
+    policy: &'a VisitPolicy,
+    end: NodeId,
+    trivial: bool,
+    // (node, visited mask, revisited mask, next edge to follow)
+    stack: Vec<(NodeId, u64, u64, usize)>,
+    path: Vec<NodeId>,
+}
+
+impl<'a> Iterator for Paths<'a> {
+    type Item = Vec<NodeId>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.trivial {
+            self.trivial = false;
+            return Some(self.path.clone());
+        }
+
+        loop {
+            let (node, mask, revisited, next_edge) = self.stack.last_mut()?;
+
+            let Some(&(neighbor, _)) = self.graph.edges(*node).get(*next_edge) else {
+                self.stack.pop();
+                self.path.pop();
+                continue;
+            };
+            *next_edge += 1;
+
+            let Some((mask, revisited)) = self.policy.enter(neighbor, *mask, *revisited) else {
+                continue;
+            };
+
+            if neighbor == self.end {
+                let mut path = self.path.clone();
+                path.push(neighbor);
+                return Some(path);
+            }
+
+            self.stack.push((neighbor, mask, revisited, 0));
+            self.path.push(neighbor);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::Graph;
+
+    use super::{count_paths, paths, VisitPolicy};
+
+    fn caves() -> Graph {
+        Graph::parse_pairs("start-A\nstart-b\nA-c\nA-b\nb-d\nA-end\nb-end", "-", false).unwrap()
+    }
+
+    fn is_small(name: &str) -> bool {
+        name.chars().all(|c| c.is_ascii_lowercase())
+    }
+
+    #[test]
+    fn small_caves_once() {
+        let graph = caves();
+        let (start, end) = (graph.id("start").unwrap(), graph.id("end").unwrap());
+        let policy = VisitPolicy::at_most_once(&graph, is_small);
+
+        assert_eq!(count_paths(&graph, start, end, &policy), 10);
+
+        let mut all_paths: Vec<String> = paths(&graph, start, end, &policy)
+            .map(|path| path.into_iter().map(|node| graph.name(node)).collect::<Vec<_>>().join(","))
+            .collect();
+        all_paths.sort();
+        assert_eq!(all_paths, vec![
+            "start,A,b,A,c,A,end",
+            "start,A,b,A,end",
+            "start,A,b,end",
+            "start,A,c,A,b,A,end",
+            "start,A,c,A,b,end",
+            "start,A,c,A,end",
+            "start,A,end",
+            "start,b,A,c,A,end",
+            "start,b,A,end",
+            "start,b,end",
+        ]);
+    }
+
+    #[test]
+    fn one_small_cave_twice() {
+        let graph = caves();
+        let (start, end) = (graph.id("start").unwrap(), graph.id("end").unwrap());
+        let policy = VisitPolicy::at_most_once(&graph, is_small).allow_one_revisit().protect(start);
+
+        assert_eq!(count_paths(&graph, start, end, &policy), 36);
+        assert_eq!(paths(&graph, start, end, &policy).count(), 36);
+    }
+
+    #[test]
+    fn each_revisit_on_a_different_node() {
+        // b can only be visited twice, even with two revisits allowed
+        let graph = Graph::parse_pairs("start-A\nA-b\nA-end", "-", false).unwrap();
+        let (start, end) = (graph.id("start").unwrap(), graph.id("end").unwrap());
+        let policy = |revisits| VisitPolicy::at_most_once(&graph, is_small).allow_revisits(revisits).protect(start);
+
+        assert_eq!(count_paths(&graph, start, end, &policy(1)), 3);
+        assert_eq!(count_paths(&graph, start, end, &policy(2)), 3);
+        assert_eq!(paths(&graph, start, end, &policy(2)).count(), 3);
+
+        let graph = Graph::parse_pairs("start-A\nA-b\nA-c\nA-end", "-", false).unwrap();
+        let (start, end) = (graph.id("start").unwrap(), graph.id("end").unwrap());
+        let policy = |revisits| VisitPolicy::at_most_once(&graph, is_small).allow_revisits(revisits).protect(start);
+
+        let counts: Vec<u64> = (0..3).map(|revisits| count_paths(&graph, start, end, &policy(revisits))).collect();
+        assert_eq!(counts, vec![5, 13, 19]);
+        assert_eq!(paths(&graph, start, end, &policy(2)).count(), 19);
+    }
+
+    #[test]
+    fn unlimited() {
+        let graph = Graph::parse_adjacency("a -> b, c\nb -> d\nc -> d\nd -> e, f\ne -> f", "->").unwrap();
+        let policy = VisitPolicy::unlimited(&graph);
+
+        assert_eq!(count_paths(&graph, 0, 5, &policy), 4);
+        assert_eq!(paths(&graph, 0, 0, &policy).collect::<Vec<_>>(), vec![vec![0]]);
+    }
+
+    #[test]
+    #[should_panic(expected = "infinitely many paths")]
+    fn infinite() {
+        let graph = Graph::parse_pairs("a-B\nB-C\nC-d", "-", false).unwrap();
+        let policy = VisitPolicy::at_most_once(&graph, is_small);
+
+        count_paths(&graph, 0, 3, &policy);
+    }
+
+    #[test]
+    fn dead_end_cycle() {
+        // B and C go around forever, but never towards d
+        let graph = Graph::parse_pairs("a-d\na-B\nB-C", "-", false).unwrap();
+        let policy = VisitPolicy::at_most_once(&graph, is_small);
+
+        assert_eq!(count_paths(&graph, 0, 1, &policy), 1);
+
+        let graph = Graph::parse_pairs("a-B\nB-C\nd-e", "-", false).unwrap();
+        assert_eq!(count_paths(&graph, 0, 3, &VisitPolicy::at_most_once(&graph, is_small)), 0);
+    }
+}
@@ -0,0 +1,264 @@
+use std::collections::HashSet;
+use std::env;
+use std::fmt::{Display, Write};
+use std::io::{IsTerminal, stdout};
+
+use crate::CustomGrid;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Color {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+}
+
+impl Color {
+    fn ansi_offset(&self) -> u8 {
+        match self {
+            Color::Black => 0,
+            Color::Red => 1,
+            Color::Green => 2,
+            Color::Yellow => 3,
+            Color::Blue => 4,
+            Color::Magenta => 5,
+            Color::Cyan => 6,
+            Color::White => 7,
+        }
+    }
+}
+
+/// How the cells of an overlay are drawn.
+///
+/// Without colors, only `replacement` is visible, so give one to overlays that must show in plain text.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Style {
+    pub foreground: Option<Color>,
+    pub background: Option<Color>,
+    pub bold: bool,
+    pub replacement: Option<char>,
+}
+
+impl Style {
+    pub fn fg(color: Color) -> Self {
+        Self {
+            foreground: Some(color),
+            ..Self::default()
+        }
+    }
+
+    pub fn bg(color: Color) -> Self {
+        Self {
+            background: Some(color),
+            ..Self::default()
+        }
+    }
+
+    pub fn bold(self) -> Self {
+        Self {
+            bold: true,
+            ..self
+        }
+    }
+
+    /// Draws `c` instead of the cell value.
+    pub fn replace(self, c: char) -> Self {
+        Self {
+            replacement: Some(c),
+            ..self
+        }
+    }
+
+    fn ansi_codes(&self) -> Vec<String> {
+        let mut codes = vec![];
+        if self.bold {
+            codes.push("1".to_string());
+        }
+        if let Some(color) = self.foreground {
+            codes.push((30 + color.ansi_offset()).to_string());
+        }
+        if let Some(color) = self.background {
+            codes.push((40 + color.ansi_offset()).to_string());
+        }
+        codes
+    }
+}
+
+/// Draws a [`CustomGrid`] with highlighted positions, e.g. to look at a path while debugging.
+pub struct Renderer {
+    overlays: Vec<(HashSet<(usize, usize)>, Style)>,
+    colors: bool,
+    rulers: bool,
+    viewport: Option<(usize, usize, usize, usize)>,
+}
+
+impl Default for Renderer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Renderer {
+    /// Colors are enabled when stdout is a terminal and `NO_COLOR` isn't set.
+    pub fn new() -> Self {
+        Self {
+            overlays: vec![],
+            colors: stdout().is_terminal() && env::var_os("NO_COLOR").is_none(),
+            rulers: false,
+            viewport: None,
+        }
+    }
+
+    pub fn colors(self, colors: bool) -> Self {
+        Self {
+            colors,
+            ..self
+        }
+    }
+
+    /// Adds row numbers on the left and column numbers on top.
+    pub fn rulers(self, rulers: bool) -> Self {
+        Self {
+            rulers,
+            ..self
+        }
+    }
+
+    /// Only draws the `rows` x `cols` rectangle whose top left corner is `(row, col)`.
+    pub fn crop(self, row: usize, col: usize, rows: usize, cols: usize) -> Self {
+        Self {
+            viewport: Some((row, col, rows, cols)),
+            ..self
+        }
+    }
+
+    /// Draws `positions` with `style`. When overlays overlap, the last one added wins.
+    pub fn overlay<I: IntoIterator<Item=(usize, usize)>>(mut self, positions: I, style: Style) -> Self {
+        self.overlays.push((positions.into_iter().collect(), style));
+        self
+    }
+
+    pub fn render<T: Display>(&self, grid: &CustomGrid<T>) -> String {
+        let (row_start, col_start, row_end, col_end) = match self.viewport {
+            None => (0, 0, grid.rows(), grid.cols()),
+            Some((row, col, rows, cols)) => (
+                row.min(grid.rows()),
+                col.min(grid.cols()),
+                (row + rows).min(grid.rows()),
+                (col + cols).min(grid.cols()),
+            ),
+        };
+        let label_width = row_end.saturating_sub(1).to_string().len();
+        let mut output = String::new();
+
+        if self.rulers {
+            if col_end > 10 {
+                output.push_str(&" ".repeat(label_width + 1));
+                for col in col_start..col_end {
+                    match col % 10 {
+                        0 => write!(output, "{}", col / 10 % 10).unwrap(),
+                        _ => output.push(' '),
+                    }
+                }
+                output.push('\n');
+            }
+            output.push_str(&" ".repeat(label_width + 1));
+            for col in col_start..col_end {
+                write!(output, "{}", col % 10).unwrap();
+            }
+            output.push('\n');
+        }
+
+        for row in row_start..row_end {
+            if self.rulers {
+                write!(output, "{:>label_width$} ", row).unwrap();
+            }
+            for col in col_start..col_end {
+                let value = grid.get(row, col).unwrap();
+                match self.style(row, col) {
+                    None => write!(output, "{}", value).unwrap(),
+                    Some(style) => self.write_styled(&mut output, value, style),
+                }
+            }
+            output.push('\n');
+        }
+
+        output
+    }
+
+    pub fn print<T: Display>(&self, grid: &CustomGrid<T>) {
+        print!("{}", self.render(grid))
+    }
+
+    fn style(&self, row: usize, col: usize) -> Option<&Style> {
+        self.overlays
+            .iter()
+            .rev()
+            .find(|(positions, _)| positions.contains(&(row, col)))
+            .map(|(_, style)| style)
+    }
+
+    fn write_styled<T: Display>(&self, output: &mut String, value: &T, style: &Style) {
+        let codes = style.ansi_codes();
+        let colored = self.colors && !codes.is_empty();
+
+        if colored {
+            write!(output, "\x1b[{}m", codes.join(";")).unwrap();
+        }
+        match style.replacement {
+            Some(c) => output.push(c),
+            None => write!(output, "{}", value).unwrap(),
+        }
+        if colored {
+            output.push_str("\x1b[0m");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::input_to_grid;
+
+    use super::{Color, Renderer, Style};
+
+    #[test]
+    fn plain_text_fallback() {
+        let grid = input_to_grid::<u8>("123\n456").unwrap();
+
+        let rendered = Renderer::new()
+            .colors(false)
+            .overlay([(0, 1)], Style::fg(Color::Red))
+            .overlay([(1, 2)], Style::bg(Color::Blue).replace('*'))
+            .render(&grid);
+
+        assert_eq!(rendered, "123\n45*\n");
+    }
+
+    #[test]
+    fn ansi_colors() {
+        let grid = input_to_grid::<u8>("12\n34").unwrap();
+
+        let rendered = Renderer::new()
+            .colors(true)
+            .overlay([(0, 0), (1, 1)], Style::fg(Color::Red))
+            .overlay([(1, 1)], Style::bg(Color::Green).bold())
+            .render(&grid);
+
+        assert_eq!(rendered, "\x1b[31m1\x1b[0m2\n3\x1b[1;42m4\x1b[0m\n");
+    }
+
+    #[test]
+    fn rulers_and_crop() {
+        let grid = input_to_grid::<char>(&["abcdefghijkl"; 12].join("\n")).unwrap();
+
+        let rendered = Renderer::new().colors(false).rulers(true).crop(8, 8, 3, 4).render(&grid);
+        assert_eq!(rendered, "     1 \n   8901\n 8 ijkl\n 9 ijkl\n10 ijkl\n");
+
+        let rendered = Renderer::new().colors(false).rulers(true).crop(0, 0, 2, 3).render(&grid);
+        assert_eq!(rendered, "  012\n0 abc\n1 abc\n");
+    }
+}
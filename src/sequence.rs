@@ -0,0 +1,226 @@
+use num::rational::Ratio;
+use num::{One, Zero};
+
+/// Exact fraction, for interpolation at points that aren't equally spaced.
+pub type Rational = Ratio<i128>;
+
+/// Gaps between consecutive values.
+pub fn differences(values: &[i128]) -> Vec<i128> {
+    values.windows(2).map(|pair| pair[1] - pair[0]).collect()
+}
+
+/// The values, then their differences, the differences of those... until they are all zeros.
+///
+/// Rows are widened to `i128`, as the gaps between `i64` values don't always fit in one.
+pub fn difference_table(values: &[i64]) -> Vec<Vec<i128>> {
+    let mut table = vec![values.iter().map(|&value| value as i128).collect::<Vec<_>>()];
+    while table.last().is_some_and(|row| row.iter().any(|&value| value != 0)) {
+        table.push(differences(table.last().unwrap()));
+    }
+    table
+}
+
+/// Value at index `n` of the sequence generated by the lowest degree polynomial through `values`,
+/// `values[i]` being at index `i`. `n` can be before the values (negative) or after them.
+///
+/// Uses Newton's forward differences formula, so it stays exact without going through fractions.
+pub fn extrapolate(values: &[i64], n: i64) -> i128 {
+    let leading: Vec<i128> = difference_table(values)
+        .iter()
+        .filter_map(|row| row.first().copied())
+        .collect();
+
+    // f(n) = sum of binomial(n, k) * (k-th difference at 0), binomial(n, k) being an integer for any integer n
+    let n = n as i128;
+    let mut binomial = 1i128;
+    let mut value = 0;
+    for (k, difference) in leading.into_iter().enumerate() {
+        let k = k as i128;
+        if k > 0 {
+            binomial = binomial * (n - k + 1) / k;
+        }
+        value += binomial * difference;
+    }
+
+    value
+}
+
+/// Polynomial with exact rational coefficients, lowest degree first.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Polynomial {
+    coefficients: Vec<Rational>,
+}
+
+impl Polynomial {
+    pub fn new(coefficients: Vec<Rational>) -> Self {
+        let mut polynomial = Self {
+            coefficients,
+        };
+        polynomial.trim();
+        polynomial
+    }
+
+    pub fn from_integers(coefficients: &[i64]) -> Self {
+        Self::new(coefficients.iter().map(|&c| Rational::from_integer(c as i128)).collect())
+    }
+
+    // Drops leading zero coefficients so that equal polynomials compare equal
+    fn trim(&mut self) {
+        while self.coefficients.last().is_some_and(Rational::is_zero) {
+            self.coefficients.pop();
+        }
+    }
+
+    pub fn coefficients(&self) -> &[Rational] {
+        &self.coefficients
+    }
+
+    /// `None` for the zero polynomial.
+    pub fn degree(&self) -> Option<usize> {
+        self.coefficients.len().checked_sub(1)
+    }
+
+    pub fn eval(&self, x: Rational) -> Rational {
+        // Horner's method
+        self.coefficients.iter().rev().fold(Rational::zero(), |acc, &c| acc * x + c)
+    }
+
+    /// Value at an integer, `None` if it isn't an integer or doesn't fit.
+    pub fn eval_integer(&self, x: i64) -> Option<i64> {
+        let value = self.eval(Rational::from_integer(x as i128));
+        if value.is_integer() {
+            i64::try_from(value.to_integer()).ok()
+        } else {
+            None
+        }
+    }
+
+    fn add(&self, other: &Polynomial) -> Polynomial {
+        let len = self.coefficients.len().max(other.coefficients.len());
+        let coefficient = |p: &Polynomial, i: usize| p.coefficients.get(i).copied().unwrap_or_else(Rational::zero);
+        Polynomial::new((0..len).map(|i| coefficient(self, i) + coefficient(other, i)).collect())
+    }
+
+    fn scale(&self, factor: Rational) -> Polynomial {
+        Polynomial::new(self.coefficients.iter().map(|&c| c * factor).collect())
+    }
+
+    // Multiplies by (x - root)
+    fn times_root(&self, root: Rational) -> Polynomial {
+        let mut shifted = vec![Rational::zero()];
+        shifted.extend_from_slice(&self.coefficients);
+        Polynomial::new(shifted).add(&self.scale(-root))
+    }
+
+    /// Lowest degree polynomial through the `(x, y)` points, by Lagrange's formula.
+    ///
+    /// # Panics
+    ///
+    /// If two points have the same `x`.
+    pub fn lagrange(points: &[(i64, i64)]) -> Polynomial {
+        let points = rational_points(points);
+
+        points.iter().enumerate().fold(Polynomial::new(vec![]), |sum, (i, &(xi, yi))| {
+            // Basis polynomial: 1 at xi, 0 at every other x
+            let basis = points
+                .iter()
+                .enumerate()
+                .filter(|&(j, _)| j != i)
+                .fold(Polynomial::new(vec![Rational::one()]), |basis, (_, &(xj, _))| {
+                    assert_ne!(xi, xj, "two points with the same x");
+                    basis.times_root(xj).scale((xi - xj).recip())
+                });
+            sum.add(&basis.scale(yi))
+        })
+    }
+
+    /// Lowest degree polynomial through the `(x, y)` points, by Newton's divided differences.
+    ///
+    /// # Panics
+    ///
+    /// If two points have the same `x`.
+    pub fn newton(points: &[(i64, i64)]) -> Polynomial {
+        let points = rational_points(points);
+
+        let mut divided: Vec<Rational> = points.iter().map(|&(_, y)| y).collect();
+        for level in 1..points.len() {
+            for i in (level..points.len()).rev() {
+                let dx = points[i].0 - points[i - level].0;
+                assert!(!dx.is_zero(), "two points with the same x");
+                divided[i] = (divided[i] - divided[i - 1]) / dx;
+            }
+        }
+
+        // Nested form, from the innermost term: c0 + (x - x0) * (c1 + (x - x1) * (c2 + ...))
+        divided
+            .iter()
+            .zip(points.iter())
+            .rev()
+            .fold(Polynomial::new(vec![]), |acc, (&c, &(x, _))| {
+                acc.times_root(x).add(&Polynomial::new(vec![c]))
+            })
+    }
+}
+
+fn rational_points(points: &[(i64, i64)]) -> Vec<(Rational, Rational)> {
+    points
+        .iter()
+        .map(|&(x, y)| (Rational::from_integer(x as i128), Rational::from_integer(y as i128)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{difference_table, extrapolate, Polynomial, Rational};
+
+    #[test]
+    fn finite_differences() {
+        assert_eq!(difference_table(&[1, 3, 6, 10]), vec![vec![1, 3, 6, 10], vec![2, 3, 4], vec![1, 1], vec![0]]);
+
+        assert_eq!(extrapolate(&[0, 3, 6, 9, 12, 15], 6), 18);
+        assert_eq!(extrapolate(&[10, 13, 16, 21, 30, 45], 6), 68);
+        assert_eq!(extrapolate(&[10, 13, 16, 21, 30, 45], -1), 5);
+        assert_eq!(extrapolate(&[7, 7, 7], 1_000_000), 7);
+        assert_eq!(extrapolate(&[], 3), 0);
+
+        // Gaps that don't fit in an i64
+        assert_eq!(difference_table(&[i64::MIN, i64::MAX])[1], vec![u64::MAX as i128]);
+        assert_eq!(extrapolate(&[i64::MIN, i64::MAX], 2), 3 * i64::MAX as i128 + 1);
+    }
+
+    #[test]
+    fn extrapolation_matches_polynomial() {
+        for coefficients in [[3, -2, 5, 1], [0, 0, 0, -7], [-1_000_000, 17, 0, 2]] {
+            let polynomial = Polynomial::from_integers(&coefficients);
+            let samples: Vec<i64> = (0..6).map(|x| polynomial.eval_integer(x).unwrap()).collect();
+
+            for n in [-50, -1, 0, 3, 6, 1000] {
+                assert_eq!(extrapolate(&samples, n), polynomial.eval_integer(n).unwrap() as i128);
+            }
+        }
+    }
+
+    #[test]
+    fn interpolation() {
+        // 2x² - 3x + 1 sampled at uneven steps
+        let points = [(-2, 15), (1, 0), (5, 36), (6, 55)];
+        let expected = Polynomial::from_integers(&[1, -3, 2]);
+
+        assert_eq!(Polynomial::lagrange(&points), expected);
+        assert_eq!(Polynomial::newton(&points), expected);
+        assert_eq!(expected.degree(), Some(2));
+        assert_eq!(expected.eval_integer(100), Some(19701));
+
+        // Through (0, 0) and (2, 1): x / 2
+        let half = Polynomial::newton(&[(0, 0), (2, 1)]);
+        assert_eq!(half.eval(Rational::from_integer(3)), Rational::new(3, 2));
+        assert_eq!(half.eval_integer(3), None);
+        assert_eq!(Polynomial::lagrange(&[(0, 0), (2, 1)]), half);
+    }
+
+    #[test]
+    #[should_panic(expected = "same x")]
+    fn duplicate_x() {
+        Polynomial::newton(&[(1, 2), (1, 3)]);
+    }
+}
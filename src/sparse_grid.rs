@@ -0,0 +1,315 @@
+use std::collections::HashMap;
+use std::fmt::{Debug, Display, Formatter};
+
+use grid::Grid;
+
+use crate::CustomGrid;
+
+/// Unbounded grid keyed by signed `(row, col)` coordinates.
+///
+/// Only the cells that were set are stored, and the bounding box grows (and shrinks) with them,
+/// so simulations spreading in any direction don't need to know their size up front.
+#[derive(Clone)]
+pub struct SparseGrid<T> {
+    cells: HashMap<(isize, isize), T>,
+    // ((min_row, min_col), (max_row, max_col)), both included
+    bounds: Option<((isize, isize), (isize, isize))>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        Self {
+            cells: HashMap::new(),
+            bounds: None,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn get(&self, row: isize, col: isize) -> Option<&T> {
+        self.cells.get(&(row, col))
+    }
+
+    pub fn get_mut(&mut self, row: isize, col: isize) -> Option<&mut T> {
+        self.cells.get_mut(&(row, col))
+    }
+
+    pub fn contains(&self, row: isize, col: isize) -> bool {
+        self.cells.contains_key(&(row, col))
+    }
+
+    pub fn insert(&mut self, row: isize, col: isize, value: T) -> Option<T> {
+        self.extend_bounds(row, col);
+        self.cells.insert((row, col), value)
+    }
+
+    /// Mutable access to a cell, inserting `default` first if it is not set yet.
+    pub fn get_mut_or_insert(&mut self, row: isize, col: isize, default: T) -> &mut T {
+        self.extend_bounds(row, col);
+        self.cells.entry((row, col)).or_insert(default)
+    }
+
+    pub fn remove(&mut self, row: isize, col: isize) -> Option<T> {
+        let removed = self.cells.remove(&(row, col));
+
+        if removed.is_some() {
+            if let Some(((min_row, min_col), (max_row, max_col))) = self.bounds {
+                if row == min_row || row == max_row || col == min_col || col == max_col {
+                    self.recompute_bounds();
+                }
+            }
+        }
+
+        removed
+    }
+
+    /// `((min_row, min_col), (max_row, max_col))` of the set cells, both corners included.
+    pub fn bounds(&self) -> Option<((isize, isize), (isize, isize))> {
+        self.bounds
+    }
+
+    pub fn rows(&self) -> usize {
+        self.bounds.map_or(0, |((min_row, _), (max_row, _))| (max_row - min_row + 1) as usize)
+    }
+
+    pub fn cols(&self) -> usize {
+        self.bounds.map_or(0, |((_, min_col), (_, max_col))| (max_col - min_col + 1) as usize)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item=&T> {
+        self.cells.values()
+    }
+
+    pub fn indexed_iter(&self) -> impl Iterator<Item=((isize, isize), &T)> {
+        self.cells.iter().map(|(&position, value)| (position, value))
+    }
+
+    pub fn indexed_iter_mut(&mut self) -> impl Iterator<Item=((isize, isize), &mut T)> {
+        self.cells.iter_mut().map(|(&position, value)| (position, value))
+    }
+
+    fn extend_bounds(&mut self, row: isize, col: isize) {
+        self.bounds = Some(match self.bounds {
+            None => ((row, col), (row, col)),
+            Some(((min_row, min_col), (max_row, max_col))) => (
+                (min_row.min(row), min_col.min(col)),
+                (max_row.max(row), max_col.max(col))
+            ),
+        });
+    }
+
+    fn recompute_bounds(&mut self) {
+        self.bounds = None;
+        let positions: Vec<_> = self.cells.keys().copied().collect();
+        for (row, col) in positions {
+            self.extend_bounds(row, col);
+        }
+    }
+
+    pub fn iter_neighbors(&self, row: isize, col: isize) -> impl Iterator<Item=((isize, isize), &T)> {
+        [(0, -1), (0, 1), (1, 0), (-1, 0)]
+            .iter()
+            .filter_map(move |(col_offset, row_offset)| {
+                let position = (row + row_offset, col + col_offset);
+                self.cells.get(&position).map(|val| (position, val))
+            })
+    }
+
+    pub fn iter_diagonal_neighbors(&self, row: isize, col: isize) -> impl Iterator<Item=((isize, isize), &T)> {
+        [(-1, -1), (-1, 0), (-1, 1), (0, -1), (0, 1), (1, -1), (1, 0), (1, 1)]
+            .iter()
+            .filter_map(move |(col_offset, row_offset)| {
+                let position = (row + row_offset, col + col_offset);
+                self.cells.get(&position).map(|val| (position, val))
+            })
+    }
+
+    pub fn right(&self, row: isize, col: isize) -> Option<&T> {
+        self.get(row, col + 1)
+    }
+    pub fn right_indexed(&self, row: isize, col: isize) -> Option<((isize, isize), &T)> {
+        self.get(row, col + 1).map(|val| ((row, col + 1), val))
+    }
+
+    pub fn left(&self, row: isize, col: isize) -> Option<&T> {
+        self.get(row, col - 1)
+    }
+    pub fn left_indexed(&self, row: isize, col: isize) -> Option<((isize, isize), &T)> {
+        self.get(row, col - 1).map(|val| ((row, col - 1), val))
+    }
+
+    pub fn up(&self, row: isize, col: isize) -> Option<&T> {
+        self.get(row - 1, col)
+    }
+    pub fn up_indexed(&self, row: isize, col: isize) -> Option<((isize, isize), &T)> {
+        self.get(row - 1, col).map(|val| ((row - 1, col), val))
+    }
+
+    pub fn down(&self, row: isize, col: isize) -> Option<&T> {
+        self.get(row + 1, col)
+    }
+    pub fn down_indexed(&self, row: isize, col: isize) -> Option<((isize, isize), &T)> {
+        self.get(row + 1, col).map(|val| ((row + 1, col), val))
+    }
+
+    /// First position (in row-major order) whose value matches `predicate`.
+    pub fn position<P: Fn(&T) -> bool>(&self, predicate: P) -> Option<(isize, isize)> {
+        self.cells
+            .iter()
+            .filter(|(_, value)| predicate(value))
+            .map(|(&position, _)| position)
+            .min()
+    }
+
+    /// Every position whose value matches `predicate`, in row-major order.
+    pub fn positions<P: Fn(&T) -> bool>(&self, predicate: P) -> Vec<(isize, isize)> {
+        let mut positions: Vec<_> = self.cells
+            .iter()
+            .filter(|(_, value)| predicate(value))
+            .map(|(&position, _)| position)
+            .collect();
+        positions.sort();
+        positions
+    }
+
+    /// Builds a sparse grid from the cells of `grid` matching `keep`, `(0, 0)` being the top left corner.
+    pub fn from_grid_filtered<P: Fn(&T) -> bool>(grid: CustomGrid<T>, keep: P) -> Self {
+        let cols = grid.cols();
+        let mut sparse = Self::new();
+
+        for (i, value) in grid.into_row_major_vec().into_iter().enumerate() {
+            if keep(&value) {
+                sparse.insert((i / cols) as isize, (i % cols) as isize, value);
+            }
+        }
+
+        sparse
+    }
+}
+
+impl<T: Clone> SparseGrid<T> {
+    /// Dense copy of the bounding box, unset cells being filled with `default`.
+    ///
+    /// The top left corner of the returned grid is `bounds().0`.
+    pub fn to_grid(&self, default: T) -> CustomGrid<T> {
+        let Some(((min_row, min_col), _)) = self.bounds else {
+            return CustomGrid(Grid::from_vec(vec![], 0));
+        };
+
+        let mut grid = Grid::init(self.rows(), self.cols(), default);
+        for (&(row, col), value) in self.cells.iter() {
+            grid[((row - min_row) as usize, (col - min_col) as usize)] = value.clone();
+        }
+
+        CustomGrid(grid)
+    }
+}
+
+impl<T> From<CustomGrid<T>> for SparseGrid<T> {
+    fn from(grid: CustomGrid<T>) -> Self {
+        Self::from_grid_filtered(grid, |_| true)
+    }
+}
+
+impl<T> FromIterator<((isize, isize), T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item=((isize, isize), T)>>(iter: I) -> Self {
+        let mut sparse = Self::new();
+        for ((row, col), value) in iter {
+            sparse.insert(row, col, value);
+        }
+        sparse
+    }
+}
+
+impl<T: Display> Debug for SparseGrid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let Some(((min_row, min_col), (max_row, max_col))) = self.bounds else {
+            return Ok(());
+        };
+
+        for row in min_row..=max_row {
+            for col in min_col..=max_col {
+                match self.get(row, col) {
+                    Some(value) => write!(f, "{}", value)?,
+                    None => write!(f, ".")?,
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: Display> SparseGrid<T> {
+    pub fn print(grid: &SparseGrid<T>) {
+        print!("{:?}", grid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::input_to_grid;
+
+    use super::SparseGrid;
+
+    #[test]
+    fn bounds_follow_inserts_and_removes() {
+        let mut grid = SparseGrid::new();
+        assert_eq!(grid.bounds(), None);
+
+        grid.insert(0, 0, 1);
+        grid.insert(-3, 2, 1);
+        grid.insert(4, -1, 1);
+        assert_eq!(grid.bounds(), Some(((-3, -1), (4, 2))));
+        assert_eq!((grid.rows(), grid.cols()), (8, 4));
+
+        grid.remove(-3, 2);
+        assert_eq!(grid.bounds(), Some(((0, -1), (4, 0))));
+
+        grid.remove(0, 0);
+        grid.remove(4, -1);
+        assert_eq!(grid.bounds(), None);
+    }
+
+    #[test]
+    fn neighbors() {
+        let grid: SparseGrid<u8> = [((-1, 0), 1), ((0, -1), 2), ((1, 1), 3), ((5, 5), 4)].into_iter().collect();
+
+        let mut neighbors: Vec<_> = grid.iter_neighbors(0, 0).collect();
+        neighbors.sort();
+        assert_eq!(neighbors, vec![((-1, 0), &1), ((0, -1), &2)]);
+
+        let mut neighbors: Vec<_> = grid.iter_diagonal_neighbors(0, 0).collect();
+        neighbors.sort();
+        assert_eq!(neighbors, vec![((-1, 0), &1), ((0, -1), &2), ((1, 1), &3)]);
+
+        assert_eq!(grid.up_indexed(0, 0), Some(((-1, 0), &1)));
+        assert_eq!(grid.left(0, 0), Some(&2));
+        assert_eq!(grid.right(0, 0), None);
+    }
+
+    #[test]
+    fn dense_round_trip() {
+        let dense = input_to_grid::<char>("#..\n.#.\n..#").unwrap();
+        let sparse = SparseGrid::from_grid_filtered(dense, |&c| c == '#');
+
+        assert_eq!(sparse.len(), 3);
+        assert_eq!(sparse.positions(|&c| c == '#'), vec![(0, 0), (1, 1), (2, 2)]);
+        assert_eq!(format!("{:?}", sparse), "#..\n.#.\n..#\n");
+
+        let dense = sparse.to_grid('.');
+        assert_eq!(format!("{:?}", dense), "#..\n.#.\n..#\n");
+    }
+}